@@ -0,0 +1,128 @@
+use std::io::{self, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+use crate::server::{
+    parse_outcome_name, parse_shape_name, protocol_error, read_message, shape_name,
+};
+use crate::{Outcome, Shape};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundResult {
+    pub round: usize,
+    pub my_move: Shape,
+    pub their_move: Shape,
+    pub outcome: Outcome,
+    pub score: i32,
+    pub my_total: i32,
+    pub their_total: i32,
+}
+
+pub struct MatchClient {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    pub player: usize,
+}
+
+fn parse_field<T>(fields: &[&str], index: usize, parse: impl Fn(&str) -> Option<T>) -> Option<T> {
+    fields.get(index).and_then(|field| parse(field))
+}
+
+fn parse_result(round: usize, message: &str) -> Option<RoundResult> {
+    let fields = message
+        .strip_prefix("RESULT ")?
+        .split(' ')
+        .collect::<Vec<_>>();
+
+    Some(RoundResult {
+        round,
+        my_move: parse_field(&fields, 0, parse_shape_name)?,
+        their_move: parse_field(&fields, 1, parse_shape_name)?,
+        outcome: parse_field(&fields, 2, parse_outcome_name)?,
+        score: parse_field(&fields, 3, |f| f.parse().ok())?,
+        my_total: parse_field(&fields, 4, |f| f.parse().ok())?,
+        their_total: parse_field(&fields, 5, |f| f.parse().ok())?,
+    })
+}
+
+impl MatchClient {
+    pub fn connect(addr: impl ToSocketAddrs, name: &str) -> io::Result<Self> {
+        let mut writer = TcpStream::connect(addr)?;
+        let mut reader = BufReader::new(writer.try_clone()?);
+        writeln!(writer, "HELLO {name}")?;
+
+        let message = read_message(&mut reader)?;
+        let player = message
+            .strip_prefix("WELCOME ")
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| protocol_error(format!("Expected WELCOME! ({message})")))?;
+
+        Ok(Self {
+            reader,
+            writer,
+            player,
+        })
+    }
+
+    // waits for the opponent, then asks `strategy` for a move every round given the results so far
+    pub fn play(
+        &mut self,
+        mut strategy: impl FnMut(&[RoundResult]) -> Shape,
+    ) -> io::Result<Vec<RoundResult>> {
+        let mut results: Vec<RoundResult> = Vec::new();
+
+        loop {
+            let message = read_message(&mut self.reader)?;
+            let mut fields = message.split(' ');
+
+            match fields.next() {
+                Some("START") => {}
+                Some("ROUND") => {
+                    let round = fields
+                        .next()
+                        .and_then(|n| n.parse().ok())
+                        .ok_or_else(|| protocol_error(format!("Bad round! ({message})")))?;
+                    let my_move = strategy(&results);
+                    writeln!(self.writer, "MOVE {}", shape_name(my_move))?;
+
+                    let reply = read_message(&mut self.reader)?;
+                    let result = parse_result(round, &reply)
+                        .ok_or_else(|| protocol_error(format!("Expected RESULT! ({reply})")))?;
+                    results.push(result);
+                }
+                Some("END") => return Ok(results),
+                _ => return Err(protocol_error(format!("Unexpected message! ({message})"))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::{MatchServer, READ_TIMEOUT};
+    use std::thread;
+
+    #[test]
+    fn plays_a_match_between_two_clients() {
+        let server = MatchServer::bind(0, 3, READ_TIMEOUT).unwrap();
+        let addr = server.local_addr().unwrap();
+        let handle = thread::spawn(move || server.run_match());
+
+        let mut rocky = MatchClient::connect(addr, "rocky").unwrap();
+        let rocky = thread::spawn(move || rocky.play(|_| Shape::Rock).unwrap());
+        let mut cycler = MatchClient::connect(addr, "cycler").unwrap();
+        let cycle = [Shape::Rock, Shape::Paper, Shape::Scissors];
+        let cycler_results = cycler.play(|history| cycle[history.len()]).unwrap();
+        let rocky_results = rocky.join().unwrap();
+
+        let summary = handle.join().unwrap().unwrap();
+        assert_eq!(summary.names, ["rocky".to_owned(), "cycler".to_owned()]);
+        // draw, loss and win for rocky: 4 + 1 + 7
+        assert_eq!(summary.totals, [12, 15]);
+
+        assert_eq!(rocky_results[1].outcome, Outcome::Loss);
+        assert_eq!(cycler_results[1].their_move, Shape::Rock);
+        assert_eq!(cycler_results[2].my_total, 15);
+        assert_eq!(cycler_results[2].their_total, 12);
+    }
+}
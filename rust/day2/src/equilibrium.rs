@@ -0,0 +1,232 @@
+use crate::{play_round, Shape};
+
+const EPSILON: f64 = 1e-9;
+
+// payoffs are from the point of view of the row player, who maximizes them,
+// while the column player tries to minimize them (zero-sum)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayoffMatrix {
+    payoffs: Vec<Vec<f64>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equilibrium {
+    pub row_strategy: Vec<f64>,
+    pub column_strategy: Vec<f64>,
+    // expected payoff of the row player, the column player gets the opposite
+    pub value: f64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EquilibriumError {
+    EmptyMatrix,
+    RaggedMatrix,
+    // NaN or infinite, 0-based
+    NonFinitePayoff { row: usize, column: usize },
+}
+
+impl PayoffMatrix {
+    pub fn new(payoffs: Vec<Vec<f64>>) -> Result<Self, EquilibriumError> {
+        let columns = payoffs.first().map_or(0, Vec::len);
+
+        if columns == 0 {
+            Err(EquilibriumError::EmptyMatrix)
+        } else if payoffs.iter().any(|row| row.len() != columns) {
+            Err(EquilibriumError::RaggedMatrix)
+        } else if let Some((row, column)) = payoffs
+            .iter()
+            .enumerate()
+            .find_map(|(i, row)| row.iter().position(|p| !p.is_finite()).map(|j| (i, j)))
+        {
+            Err(EquilibriumError::NonFinitePayoff { row, column })
+        } else {
+            Ok(Self { payoffs })
+        }
+    }
+
+    // builds the matrix for any rule table, `payoff(row_move, column_move)` scoring the row player
+    pub fn from_rules<S: Copy>(
+        row_moves: &[S],
+        column_moves: &[S],
+        payoff: impl Fn(S, S) -> f64,
+    ) -> Result<Self, EquilibriumError> {
+        Self::new(
+            row_moves
+                .iter()
+                .map(|&r| column_moves.iter().map(|&c| payoff(r, c)).collect())
+                .collect(),
+        )
+    }
+
+    // my score from `play_round` against the opponent's shape, shape bonuses included
+    pub fn rock_paper_scissors() -> Self {
+        let shapes = [Shape::Rock, Shape::Paper, Shape::Scissors];
+        Self::from_rules(&shapes, &shapes, |mine, theirs| {
+            play_round(theirs, mine) as f64
+        })
+        .unwrap()
+    }
+
+    pub fn solve(&self) -> Equilibrium {
+        // shifting every payoff to be positive keeps the game value positive,
+        // which is what the LP reformulation below relies on
+        let min = self
+            .payoffs
+            .iter()
+            .flatten()
+            .fold(f64::INFINITY, |acc, &p| acc.min(p));
+        let shift = 1.0 - min;
+        let shifted = self
+            .payoffs
+            .iter()
+            .map(|row| row.iter().map(|p| p + shift).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        // column player: maximize sum(y) subject to A y <= 1, y >= 0;
+        // the duals of the constraints are the row player's strategy
+        let (primal, dual, optimum) = simplex(&shifted);
+
+        Equilibrium {
+            row_strategy: dual.iter().map(|x| x / optimum).collect(),
+            column_strategy: primal.iter().map(|y| y / optimum).collect(),
+            value: 1.0 / optimum - shift,
+        }
+    }
+}
+
+// maximizes sum(y) subject to `constraints` y <= 1 and y >= 0, for a strictly positive matrix,
+// returning the primal solution, the dual solution and the optimal objective
+fn simplex(constraints: &[Vec<f64>]) -> (Vec<f64>, Vec<f64>, f64) {
+    let rows = constraints.len();
+    let columns = constraints[0].len();
+    let width = columns + rows + 1;
+
+    let mut tableau = constraints
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut line = vec![0.0; width];
+            line[..columns].copy_from_slice(row);
+            line[columns + i] = 1.0;
+            line[width - 1] = 1.0;
+            line
+        })
+        .collect::<Vec<_>>();
+    let mut objective = vec![0.0; width];
+    objective[..columns].fill(-1.0);
+
+    let mut basis = (columns..columns + rows).collect::<Vec<_>>();
+
+    // Bland's rule: lowest entering index and lowest leaving basis index, so it never cycles
+    while let Some(entering) = (0..width - 1).find(|&j| objective[j] < -EPSILON) {
+        let leaving = (0..rows)
+            .filter(|&i| tableau[i][entering] > EPSILON)
+            .min_by(|&a, &b| {
+                let ratio_a = tableau[a][width - 1] / tableau[a][entering];
+                let ratio_b = tableau[b][width - 1] / tableau[b][entering];
+                ratio_a
+                    .partial_cmp(&ratio_b)
+                    .unwrap()
+                    .then(basis[a].cmp(&basis[b]))
+            })
+            .expect("Bounded by construction: every coefficient is positive");
+
+        let pivot = tableau[leaving][entering];
+        tableau[leaving].iter_mut().for_each(|v| *v /= pivot);
+        let pivot_row = tableau[leaving].clone();
+
+        for (i, row) in tableau.iter_mut().enumerate() {
+            if i != leaving {
+                let factor = row[entering];
+                row.iter_mut()
+                    .zip(&pivot_row)
+                    .for_each(|(v, p)| *v -= factor * p);
+            }
+        }
+        let factor = objective[entering];
+        objective
+            .iter_mut()
+            .zip(&pivot_row)
+            .for_each(|(v, p)| *v -= factor * p);
+
+        basis[leaving] = entering;
+    }
+
+    let mut primal = vec![0.0; columns];
+    for (i, &variable) in basis.iter().enumerate() {
+        if variable < columns {
+            primal[variable] = tableau[i][width - 1];
+        }
+    }
+    let dual = objective[columns..columns + rows].to_vec();
+
+    (primal, dual, objective[width - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-6, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn solves_a_two_by_two_game() {
+        let game = PayoffMatrix::new(vec![vec![3.0, -1.0], vec![-2.0, 1.0]]).unwrap();
+        let solution = game.solve();
+
+        assert_close(&solution.row_strategy, &[3.0 / 7.0, 4.0 / 7.0]);
+        assert_close(&solution.column_strategy, &[2.0 / 7.0, 5.0 / 7.0]);
+        assert_close(&[solution.value], &[1.0 / 7.0]);
+    }
+
+    #[test]
+    fn solves_the_score_table_of_play_round() {
+        let solution = PayoffMatrix::rock_paper_scissors().solve();
+
+        // the opponent avoids paper, which my scissors beat for the biggest score
+        assert_close(&solution.row_strategy, &[1.0 / 3.0; 3]);
+        assert_close(
+            &solution.column_strategy,
+            &[4.0 / 9.0, 1.0 / 9.0, 4.0 / 9.0],
+        );
+        assert_close(&[solution.value], &[5.0]);
+    }
+
+    #[test]
+    fn solves_any_rule_table() {
+        // rock-paper-scissors-lizard-spock: each move beats two of the other four
+        let moves = [0, 1, 2, 3, 4];
+        let game = PayoffMatrix::from_rules(&moves, &moves, |a, b| match (5 + b - a) % 5 {
+            0 => 0.0,
+            1 | 3 => -1.0,
+            _ => 1.0,
+        })
+        .unwrap();
+        let solution = game.solve();
+
+        assert_close(&solution.row_strategy, &[0.2; 5]);
+        assert_close(&solution.column_strategy, &[0.2; 5]);
+        assert_close(&[solution.value], &[0.0]);
+    }
+
+    #[test]
+    fn rejects_malformed_matrices() {
+        assert_eq!(
+            PayoffMatrix::new(vec![]).unwrap_err(),
+            EquilibriumError::EmptyMatrix
+        );
+        assert_eq!(
+            PayoffMatrix::new(vec![vec![1.0], vec![1.0, 2.0]]).unwrap_err(),
+            EquilibriumError::RaggedMatrix
+        );
+        assert_eq!(
+            PayoffMatrix::new(vec![vec![1.0, 2.0], vec![f64::NAN, f64::INFINITY]]).unwrap_err(),
+            EquilibriumError::NonFinitePayoff { row: 1, column: 0 }
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use std::{error::Error, fmt};
+
+use crate::{parse_my_move, parse_opponent_move, parse_outcome, Outcome, Shape};
+
+const OPPONENT_SYMBOLS: &[&str] = &["A", "B", "C"];
+const SECOND_COLUMN_SYMBOLS: &[&str] = &["X", "Y", "Z"];
+const END_OF_LINE: &[&str] = &["end of line"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    // 1-based, in characters; points just past the end of the line for missing tokens
+    pub column: usize,
+    // empty when a token is missing
+    pub token: String,
+    pub expected: &'static [&'static str],
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let found = if self.token.is_empty() {
+            "nothing".to_owned()
+        } else {
+            format!("\"{}\"", self.token)
+        };
+
+        write!(
+            f,
+            "line {}, column {}: found {found}, expected one of {}",
+            self.line,
+            self.column,
+            self.expected.join(", ")
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    // the first bad round aborts parsing
+    Strict,
+    // bad rounds are left out and reported in `StrategyGuide::skipped`
+    SkipInvalid,
+}
+
+// the second column decodes as both interpretations, so callers pick the one they need
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuideRound {
+    pub line: usize,
+    pub opponent_move: Shape,
+    pub my_move: Shape,
+    pub outcome: Outcome,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct StrategyGuide {
+    pub rounds: Vec<GuideRound>,
+    pub skipped: Vec<ParseError>,
+}
+
+// whitespace separated tokens with their 1-based character column
+fn tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.split(char::is_whitespace)
+        .scan(1, |column, token| {
+            let token_column = *column;
+            *column += token.chars().count() + 1;
+            Some((token_column, token))
+        })
+        .filter(|(_, token)| !token.is_empty())
+}
+
+fn parse_round(number: usize, line: &str) -> Result<GuideRound, ParseError> {
+    let error = |column, token: &str, expected| ParseError {
+        line: number,
+        column,
+        token: token.to_owned(),
+        expected,
+    };
+    let end_column = line.chars().count() + 1;
+    let mut tokens = tokens(line);
+
+    let (column, opponent) = tokens
+        .next()
+        .ok_or_else(|| error(end_column, "", OPPONENT_SYMBOLS))?;
+    let opponent_move =
+        parse_opponent_move(opponent).ok_or_else(|| error(column, opponent, OPPONENT_SYMBOLS))?;
+
+    let (column, second) = tokens
+        .next()
+        .ok_or_else(|| error(end_column, "", SECOND_COLUMN_SYMBOLS))?;
+    let (my_move, outcome) = parse_my_move(second)
+        .zip(parse_outcome(second))
+        .ok_or_else(|| error(column, second, SECOND_COLUMN_SYMBOLS))?;
+
+    if let Some((column, extra)) = tokens.next() {
+        return Err(error(column, extra, END_OF_LINE));
+    }
+
+    Ok(GuideRound {
+        line: number,
+        opponent_move,
+        my_move,
+        outcome,
+    })
+}
+
+// blank lines are ignored, trailing whitespace and CRLF line endings are tolerated
+pub fn parse_strategy(strategy: &str, mode: ParseMode) -> Result<StrategyGuide, ParseError> {
+    let mut guide = StrategyGuide::default();
+
+    for (index, line) in strategy.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            continue;
+        }
+
+        match (parse_round(index + 1, line), mode) {
+            (Ok(round), _) => guide.rounds.push(round),
+            (Err(error), ParseMode::SkipInvalid) => guide.skipped.push(error),
+            (Err(error), ParseMode::Strict) => return Err(error),
+        }
+    }
+
+    Ok(guide)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tolerates_crlf_and_trailing_whitespace() {
+        let guide = parse_strategy("A Y\r\nB X  \r\n\nC Z\t", ParseMode::Strict).unwrap();
+
+        assert_eq!(guide.rounds.len(), 3);
+        assert_eq!(guide.rounds[2].line, 4);
+        assert_eq!(guide.rounds[2].opponent_move, Shape::Scissors);
+        assert_eq!(guide.rounds[2].outcome, Outcome::Win);
+    }
+
+    #[test]
+    fn reports_location_and_expected_tokens() {
+        let error = parse_strategy("A Y\nB  Q\n", ParseMode::Strict).unwrap_err();
+
+        assert_eq!(
+            error,
+            ParseError {
+                line: 2,
+                column: 4,
+                token: "Q".to_owned(),
+                expected: SECOND_COLUMN_SYMBOLS,
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 4: found \"Q\", expected one of X, Y, Z"
+        );
+    }
+
+    #[test]
+    fn skips_and_reports_bad_rounds() {
+        let guide = parse_strategy("A Y\nD X\nB\nC Z Z\nC X", ParseMode::SkipInvalid).unwrap();
+
+        assert_eq!(guide.rounds.len(), 2);
+        assert_eq!(
+            guide
+                .skipped
+                .iter()
+                .map(|e| (e.line, e.column, e.token.as_str()))
+                .collect::<Vec<_>>(),
+            [(2, 1, "D"), (3, 2, ""), (4, 5, "Z")]
+        );
+        assert_eq!(guide.skipped[2].expected, END_OF_LINE);
+    }
+}
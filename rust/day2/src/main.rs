@@ -1,17 +1,31 @@
+use std::io;
+
+use guide::{parse_strategy, ParseError, ParseMode};
+
+mod client;
+mod equilibrium;
+mod guide;
+mod repl;
+mod server;
+mod tally;
+mod transcript;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Outcome {
     Win = 6,
     Draw = 3,
     Loss = 0,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Shape {
     Rock = 1,
     Paper = 2,
     Scissors = 3,
 }
 
-fn play_round(opponent_move: Shape, my_move: Shape) -> i32 {
-    let outcome = match (opponent_move, &my_move) {
+fn round_outcome(opponent_move: Shape, my_move: Shape) -> Outcome {
+    match (opponent_move, my_move) {
         (Shape::Rock, Shape::Paper) => Outcome::Win,
         (Shape::Rock, Shape::Scissors) => Outcome::Loss,
         (Shape::Scissors, Shape::Rock) => Outcome::Win,
@@ -19,52 +33,70 @@ fn play_round(opponent_move: Shape, my_move: Shape) -> i32 {
         (Shape::Paper, Shape::Scissors) => Outcome::Win,
         (Shape::Paper, Shape::Rock) => Outcome::Loss,
         (_, _) => Outcome::Draw,
-    };
+    }
+}
 
-    outcome as i32 + my_move as i32
+fn play_round(opponent_move: Shape, my_move: Shape) -> i32 {
+    round_outcome(opponent_move, my_move) as i32 + my_move as i32
 }
 
-fn parse_opponent_move(opponent_move: &str) -> Shape {
-    match opponent_move {
-        "A" => Shape::Rock,
-        "B" => Shape::Paper,
-        "C" => Shape::Scissors,
-        mv => panic!("Unrecognized opponent move! ({mv})"),
+// both sides of a round, each scored by `play_round` from its own point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RoundScores {
+    my_outcome: Outcome,
+    my_score: i32,
+    opponent_score: i32,
+}
+
+fn evaluate_round(opponent_move: Shape, my_move: Shape) -> RoundScores {
+    RoundScores {
+        my_outcome: round_outcome(opponent_move, my_move),
+        my_score: play_round(opponent_move, my_move),
+        opponent_score: play_round(my_move, opponent_move),
     }
 }
 
-fn parse_my_move(opponent_move: &str) -> Shape {
+fn parse_opponent_move(opponent_move: &str) -> Option<Shape> {
     match opponent_move {
-        "X" => Shape::Rock,
-        "Y" => Shape::Paper,
-        "Z" => Shape::Scissors,
-        mv => panic!("Unrecognized my move! ({mv})"),
+        "A" => Some(Shape::Rock),
+        "B" => Some(Shape::Paper),
+        "C" => Some(Shape::Scissors),
+        _ => None,
+    }
+}
+
+fn parse_my_move(my_move: &str) -> Option<Shape> {
+    match my_move {
+        "X" => Some(Shape::Rock),
+        "Y" => Some(Shape::Paper),
+        "Z" => Some(Shape::Scissors),
+        _ => None,
     }
 }
 
 // part one
-fn compute_score_for_moves() -> i32 {
-    STRATEGY
-        .lines()
-        .map(|line| {
-            let (opp, my) = line.split_once(" ").unwrap();
-            play_round(parse_opponent_move(opp), parse_my_move(my))
-        })
-        .sum()
+fn compute_score_for_moves() -> Result<i32, ParseError> {
+    let guide = parse_strategy(STRATEGY, ParseMode::Strict)?;
+
+    Ok(guide
+        .rounds
+        .iter()
+        .map(|round| play_round(round.opponent_move, round.my_move))
+        .sum())
 }
 
 // part two
-fn parse_outcome(outcome: &str) -> Outcome {
+fn parse_outcome(outcome: &str) -> Option<Outcome> {
     match outcome {
-        "X" => Outcome::Loss,
-        "Y" => Outcome::Draw,
-        "Z" => Outcome::Win,
-        o => panic!("Unrecognized outcome! ({o})"),
+        "X" => Some(Outcome::Loss),
+        "Y" => Some(Outcome::Draw),
+        "Z" => Some(Outcome::Win),
+        _ => None,
     }
 }
 
-fn compute_score_from_outcome(opponent_move: Shape, outcome: Outcome) -> i32 {
-    let my_move = match (opponent_move, &outcome) {
+fn shape_for_outcome(opponent_move: Shape, outcome: Outcome) -> Shape {
+    match (opponent_move, outcome) {
         (Shape::Rock, Outcome::Win) => Shape::Paper,
         (Shape::Rock, Outcome::Loss) => Shape::Scissors,
         (Shape::Scissors, Outcome::Win) => Shape::Rock,
@@ -72,41 +104,147 @@ fn compute_score_from_outcome(opponent_move: Shape, outcome: Outcome) -> i32 {
         (Shape::Paper, Outcome::Win) => Shape::Scissors,
         (Shape::Paper, Outcome::Loss) => Shape::Rock,
         (mv, Outcome::Draw) => mv,
-    };
+    }
+}
 
-    outcome as i32 + my_move as i32
+fn compute_score_from_outcome(opponent_move: Shape, outcome: Outcome) -> i32 {
+    outcome as i32 + shape_for_outcome(opponent_move, outcome) as i32
 }
 
-fn compute_score_for_move_and_outcome() -> i32 {
-    STRATEGY
-        .lines()
-        .map(|line| {
-            let (opp, outc) = line.split_once(" ").unwrap();
-            compute_score_from_outcome(parse_opponent_move(opp), parse_outcome(outc))
-        })
-        .sum()
+fn compute_score_for_move_and_outcome() -> Result<i32, ParseError> {
+    let guide = parse_strategy(STRATEGY, ParseMode::Strict)?;
+
+    Ok(guide
+        .rounds
+        .iter()
+        .map(|round| compute_score_from_outcome(round.opponent_move, round.outcome))
+        .sum())
 }
 
-fn main() {
-    println!("Score for moves: {}", compute_score_for_moves());
-    println!(
-        "Score for move and outcome: {}",
-        compute_score_for_move_and_outcome()
-    );
+fn main() -> io::Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    match args.first().map(String::as_str) {
+        Some("play") => {
+            let bot_name = args.get(1).map(String::as_str).unwrap_or("cycle");
+            let mut bot = repl::bot_by_name(bot_name).unwrap_or_else(|| {
+                panic!(
+                    "Unknown bot! ({bot_name}, expected one of {})",
+                    repl::BOT_NAMES.join(", ")
+                )
+            });
+            repl::run(io::stdin().lock(), io::stdout().lock(), bot.as_mut())?;
+            Ok(())
+        }
+        Some("equilibrium") => {
+            let solution = equilibrium::PayoffMatrix::rock_paper_scissors().solve();
+            println!(
+                "My strategy (rock, paper, scissors): {:?}",
+                solution.row_strategy
+            );
+            println!(
+                "Opponent strategy (rock, paper, scissors): {:?}",
+                solution.column_strategy
+            );
+            println!("Game value: {}", solution.value);
+            Ok(())
+        }
+        Some("transcript") => {
+            let rounds = transcript::transcript(STRATEGY).map_err(io::Error::other)?;
+            match args.get(1).map(String::as_str) {
+                Some("json") => println!("{}", transcript::to_json(&rounds)),
+                _ => print!("{}", transcript::to_csv(&rounds)),
+            }
+            Ok(())
+        }
+        Some("serve") => {
+            let port = args
+                .get(1)
+                .map_or(Ok(7878), |p| p.parse())
+                .expect("Bad port!");
+            let rounds = args
+                .get(2)
+                .map_or(Ok(3), |r| r.parse())
+                .expect("Bad rounds!");
+            let server = server::MatchServer::bind(port, rounds, server::READ_TIMEOUT)?;
+            println!("Listening on {}", server.local_addr()?);
+
+            // a player leaving or misbehaving only ends their match, not the server
+            loop {
+                match server.run_match() {
+                    Ok(summary) => println!(
+                        "{} {} - {} {}",
+                        summary.names[0], summary.totals[0], summary.totals[1], summary.names[1]
+                    ),
+                    Err(error) => eprintln!("Match aborted: {error}"),
+                }
+            }
+        }
+        Some("connect") => {
+            let addr = args.get(1).map_or("127.0.0.1:7878", String::as_str);
+            let name = args.get(2).map_or("guide", String::as_str);
+            let mut client = client::MatchClient::connect(addr, name)?;
+            println!("Joined as player {}", client.player);
+
+            // replays my moves from the strategy guide
+            let moves = parse_strategy(STRATEGY, ParseMode::Strict)
+                .map_err(io::Error::other)?
+                .rounds
+                .iter()
+                .map(|round| round.my_move)
+                .collect::<Vec<_>>();
+            let results = client.play(|history| moves[history.len() % moves.len()])?;
+            if let Some(last) = results.last() {
+                println!("Final score: {} - {}", last.my_total, last.their_total);
+            }
+            Ok(())
+        }
+        Some("tally") => {
+            let guide = parse_strategy(STRATEGY, ParseMode::Strict).map_err(io::Error::other)?;
+            for interpretation in [
+                tally::Interpretation::Moves,
+                tally::Interpretation::Outcomes,
+            ] {
+                let tallies = tally::tally(&guide, interpretation);
+                println!("{interpretation:?}");
+                println!("  me:       {}", tallies.me);
+                println!("  opponent: {}", tallies.opponent);
+            }
+            Ok(())
+        }
+        Some("check") => {
+            let guide =
+                parse_strategy(STRATEGY, ParseMode::SkipInvalid).map_err(io::Error::other)?;
+            println!("{} valid rounds", guide.rounds.len());
+            for error in guide.skipped {
+                println!("Skipped {error}");
+            }
+            Ok(())
+        }
+        _ => {
+            let by_moves = compute_score_for_moves().map_err(io::Error::other)?;
+            let by_outcome = compute_score_for_move_and_outcome().map_err(io::Error::other)?;
+            println!("Score for moves: {by_moves}");
+            println!("Score for move and outcome: {by_outcome}");
+            Ok(())
+        }
+    }
 }
 
+// the puzzle input stays last, below the tests
+#[allow(clippy::items_after_test_module)]
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn computes_score_for_strategy() {
-        assert_eq!(compute_score_for_moves(), 10595);
+        assert_eq!(compute_score_for_moves().unwrap(), 10595);
     }
 
     #[test]
     fn computes_score_for_move_and_outcome() {
-        assert_eq!(compute_score_for_move_and_outcome(), 9541);
+        assert_eq!(compute_score_for_move_and_outcome().unwrap(), 9541);
     }
 }
 
@@ -0,0 +1,258 @@
+use std::io::{self, BufRead, Write};
+
+use crate::guide::{parse_strategy, ParseMode};
+use crate::{evaluate_round, Outcome, Shape, STRATEGY};
+
+pub const BOT_NAMES: [&str; 6] = ["rock", "paper", "scissors", "cycle", "copycat", "guide"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    bot_move: Shape,
+    my_move: Shape,
+    outcome: Outcome,
+    my_score: i32,
+    bot_score: i32,
+}
+
+impl Round {
+    fn new(bot_move: Shape, my_move: Shape) -> Self {
+        let scores = evaluate_round(bot_move, my_move);
+
+        Self {
+            bot_move,
+            my_move,
+            outcome: scores.my_outcome,
+            my_score: scores.my_score,
+            bot_score: scores.opponent_score,
+        }
+    }
+}
+
+pub trait Bot {
+    fn name(&self) -> &str;
+
+    // the bot picks its move before seeing the human one, so it only gets the history
+    fn next_move(&mut self, history: &[Round]) -> Shape;
+}
+
+struct ConstantBot(Shape);
+struct CycleBot;
+struct CopycatBot;
+struct GuideBot(Vec<Shape>);
+
+impl Bot for ConstantBot {
+    fn name(&self) -> &str {
+        match self.0 {
+            Shape::Rock => "rock",
+            Shape::Paper => "paper",
+            Shape::Scissors => "scissors",
+        }
+    }
+
+    fn next_move(&mut self, _history: &[Round]) -> Shape {
+        self.0
+    }
+}
+
+impl Bot for CycleBot {
+    fn name(&self) -> &str {
+        "cycle"
+    }
+
+    fn next_move(&mut self, history: &[Round]) -> Shape {
+        [Shape::Rock, Shape::Paper, Shape::Scissors][history.len() % 3]
+    }
+}
+
+impl Bot for CopycatBot {
+    fn name(&self) -> &str {
+        "copycat"
+    }
+
+    fn next_move(&mut self, history: &[Round]) -> Shape {
+        history.last().map_or(Shape::Rock, |round| round.my_move)
+    }
+}
+
+// replays the opponent column of the strategy guide, starting over when it runs out
+impl Bot for GuideBot {
+    fn name(&self) -> &str {
+        "guide"
+    }
+
+    fn next_move(&mut self, history: &[Round]) -> Shape {
+        self.0[history.len() % self.0.len()]
+    }
+}
+
+pub fn bot_by_name(name: &str) -> Option<Box<dyn Bot>> {
+    match name {
+        "rock" => Some(Box::new(ConstantBot(Shape::Rock))),
+        "paper" => Some(Box::new(ConstantBot(Shape::Paper))),
+        "scissors" => Some(Box::new(ConstantBot(Shape::Scissors))),
+        "cycle" => Some(Box::new(CycleBot)),
+        "copycat" => Some(Box::new(CopycatBot)),
+        "guide" => {
+            let guide = parse_strategy(STRATEGY, ParseMode::Strict).ok()?;
+            let moves = guide.rounds.iter().map(|r| r.opponent_move).collect();
+            Some(Box::new(GuideBot(moves)))
+        }
+        _ => None,
+    }
+}
+
+fn parse_human_move(input: &str) -> Option<Shape> {
+    match input.to_lowercase().as_str() {
+        "r" | "rock" | "x" => Some(Shape::Rock),
+        "p" | "paper" | "y" => Some(Shape::Paper),
+        "s" | "scissors" | "z" => Some(Shape::Scissors),
+        _ => None,
+    }
+}
+
+pub fn outcome_name(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Win => "win",
+        Outcome::Draw => "draw",
+        Outcome::Loss => "loss",
+    }
+}
+
+fn write_history(output: &mut impl Write, history: &[Round]) -> io::Result<()> {
+    if history.is_empty() {
+        return writeln!(output, "No rounds played yet");
+    }
+
+    for (index, round) in history.iter().enumerate() {
+        writeln!(
+            output,
+            "{:>3}. you {:?} vs {:?}: {} ({} - {})",
+            index + 1,
+            round.my_move,
+            round.bot_move,
+            outcome_name(round.outcome),
+            round.my_score,
+            round.bot_score
+        )?;
+    }
+
+    Ok(())
+}
+
+fn write_summary(output: &mut impl Write, history: &[Round], bot: &dyn Bot) -> io::Result<()> {
+    let count = |outcome| history.iter().filter(|r| r.outcome == outcome).count();
+
+    writeln!(
+        output,
+        "Game over after {} rounds: {} wins, {} draws, {} losses",
+        history.len(),
+        count(Outcome::Win),
+        count(Outcome::Draw),
+        count(Outcome::Loss)
+    )?;
+    writeln!(
+        output,
+        "Final score: you {} - {} {}",
+        history.iter().map(|r| r.my_score).sum::<i32>(),
+        history.iter().map(|r| r.bot_score).sum::<i32>(),
+        bot.name()
+    )
+}
+
+// reads one command per line until "quit" or end of input, so it can be scripted through stdin
+pub fn run(
+    input: impl BufRead,
+    mut output: impl Write,
+    bot: &mut dyn Bot,
+) -> io::Result<Vec<Round>> {
+    let mut history: Vec<Round> = Vec::new();
+    let (mut my_total, mut bot_total) = (0, 0);
+
+    writeln!(
+        output,
+        "Playing against the {} bot. Enter rock, paper or scissors (r/p/s), \"history\" or \"quit\".",
+        bot.name()
+    )?;
+
+    for line in input.lines() {
+        let line = line?;
+        let command = line.trim();
+
+        match command {
+            "" => continue,
+            "q" | "quit" => break,
+            "h" | "history" => {
+                write_history(&mut output, &history)?;
+                continue;
+            }
+            _ => {}
+        }
+
+        let Some(my_move) = parse_human_move(command) else {
+            writeln!(output, "Unrecognized move! ({command})")?;
+            continue;
+        };
+
+        let round = Round::new(bot.next_move(&history), my_move);
+        my_total += round.my_score;
+        bot_total += round.bot_score;
+        history.push(round);
+
+        writeln!(
+            output,
+            "Round {}: you played {:?}, {} played {:?}: {} (+{}, {} +{})",
+            history.len(),
+            round.my_move,
+            bot.name(),
+            round.bot_move,
+            outcome_name(round.outcome),
+            round.my_score,
+            bot.name(),
+            round.bot_score
+        )?;
+        writeln!(output, "Score: you {my_total} - {bot_total} {}", bot.name())?;
+    }
+
+    write_summary(&mut output, &history, bot)?;
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play_script(script: &str, bot_name: &str) -> (Vec<Round>, String) {
+        let mut bot = bot_by_name(bot_name).unwrap();
+        let mut output = Vec::new();
+        let history = run(script.as_bytes(), &mut output, bot.as_mut()).unwrap();
+        (history, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn plays_scripted_rounds_against_cycle_bot() {
+        let (history, output) = play_script("paper\ns\nbogus\nR\nquit\nrock\n", "cycle");
+
+        // cycle plays rock, paper, scissors: win, win, win
+        assert_eq!(history.len(), 3);
+        assert!(history.iter().all(|r| r.outcome == Outcome::Win));
+        assert!(output.contains("Unrecognized move! (bogus)"));
+        assert!(output.contains("Score: you 8 - 1 cycle"));
+        assert!(output.contains("Game over after 3 rounds: 3 wins, 0 draws, 0 losses"));
+        assert!(output.contains("Final score: you 24 - 6 cycle"));
+    }
+
+    #[test]
+    fn copycat_repeats_the_previous_human_move() {
+        let (history, output) = play_script("scissors\nscissors\nhistory\n", "copycat");
+
+        assert_eq!(history[0].outcome, Outcome::Loss);
+        assert_eq!(history[1].outcome, Outcome::Draw);
+        assert!(output.contains("  2. you Scissors vs Scissors: draw (6 - 6)"));
+    }
+
+    #[test]
+    fn rejects_unknown_bots() {
+        assert!(bot_by_name("skynet").is_none());
+        assert!(BOT_NAMES.iter().all(|name| bot_by_name(name).is_some()));
+    }
+}
@@ -0,0 +1,256 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::time::Duration;
+
+use crate::repl::outcome_name;
+use crate::{evaluate_round, Outcome, Shape};
+
+// Line based protocol, one message per line:
+//
+//   client -> server  HELLO <name>
+//   server -> client  WELCOME <player number>
+//   server -> client  START <opponent name> <rounds>
+//   server -> client  ROUND <round number>
+//   client -> server  MOVE <rock|paper|scissors>
+//   server -> client  RESULT <my shape> <their shape> <outcome> <score> <my total> <their total>
+//   server -> client  END <my total> <their total>
+//
+// an unrecognized MOVE gets an `ERROR <reason>` reply and the server waits for another one
+
+// how long a player may take to send a message before their match is aborted
+pub const READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub fn shape_name(shape: Shape) -> &'static str {
+    match shape {
+        Shape::Rock => "rock",
+        Shape::Paper => "paper",
+        Shape::Scissors => "scissors",
+    }
+}
+
+pub fn parse_shape_name(name: &str) -> Option<Shape> {
+    match name {
+        "rock" => Some(Shape::Rock),
+        "paper" => Some(Shape::Paper),
+        "scissors" => Some(Shape::Scissors),
+        _ => None,
+    }
+}
+
+pub fn parse_outcome_name(name: &str) -> Option<Outcome> {
+    match name {
+        "win" => Some(Outcome::Win),
+        "draw" => Some(Outcome::Draw),
+        "loss" => Some(Outcome::Loss),
+        _ => None,
+    }
+}
+
+pub fn protocol_error(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<String> {
+    let mut line = String::new();
+    let read = reader
+        .read_line(&mut line)
+        .map_err(|error| match error.kind() {
+            // what a read timeout looks like, depending on the platform
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+                io::Error::new(io::ErrorKind::TimedOut, "No message in time")
+            }
+            _ => error,
+        })?;
+    if read == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "Connection closed",
+        ));
+    }
+    Ok(line.trim_end().to_owned())
+}
+
+struct Player {
+    name: String,
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    total: i32,
+}
+
+impl Player {
+    fn join(stream: TcpStream, number: usize, timeout: Duration) -> io::Result<Self> {
+        // a silent player would otherwise hold up the server forever
+        stream.set_read_timeout(Some(timeout))?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let message = read_message(&mut reader)?;
+        let name = message
+            .strip_prefix("HELLO ")
+            .ok_or_else(|| protocol_error(format!("Expected HELLO! ({message})")))?
+            .to_owned();
+
+        let mut player = Self {
+            name,
+            reader,
+            writer: stream,
+            total: 0,
+        };
+        player.send(&format!("WELCOME {number}"))?;
+        Ok(player)
+    }
+
+    fn send(&mut self, message: &str) -> io::Result<()> {
+        writeln!(self.writer, "{message}")
+    }
+
+    fn receive_move(&mut self) -> io::Result<Shape> {
+        loop {
+            let message = read_message(&mut self.reader)?;
+            match message.strip_prefix("MOVE ").and_then(parse_shape_name) {
+                Some(shape) => return Ok(shape),
+                None => self.send(&format!("ERROR Unrecognized move! ({message})"))?,
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct MatchSummary {
+    pub names: [String; 2],
+    pub totals: [i32; 2],
+}
+
+pub struct MatchServer {
+    listener: TcpListener,
+    rounds: usize,
+    read_timeout: Duration,
+}
+
+impl MatchServer {
+    // only ever listens on the loopback interface; port 0 picks a free one
+    pub fn bind(port: u16, rounds: usize, read_timeout: Duration) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind((Ipv4Addr::LOCALHOST, port))?,
+            rounds,
+            read_timeout,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    pub fn run_match(&self) -> io::Result<MatchSummary> {
+        let mut players = [
+            Player::join(self.listener.accept()?.0, 1, self.read_timeout)?,
+            Player::join(self.listener.accept()?.0, 2, self.read_timeout)?,
+        ];
+
+        for (me, them) in [(0, 1), (1, 0)] {
+            let start = format!("START {} {}", players[them].name, self.rounds);
+            players[me].send(&start)?;
+        }
+
+        for round in 1..=self.rounds {
+            for player in players.iter_mut() {
+                player.send(&format!("ROUND {round}"))?;
+            }
+
+            let moves = [players[0].receive_move()?, players[1].receive_move()?];
+            let scores = [
+                evaluate_round(moves[1], moves[0]),
+                evaluate_round(moves[0], moves[1]),
+            ];
+            players[0].total += scores[0].my_score;
+            players[1].total += scores[0].opponent_score;
+
+            for (me, them) in [(0, 1), (1, 0)] {
+                let result = format!(
+                    "RESULT {} {} {} {} {} {}",
+                    shape_name(moves[me]),
+                    shape_name(moves[them]),
+                    outcome_name(scores[me].my_outcome),
+                    scores[me].my_score,
+                    players[me].total,
+                    players[them].total
+                );
+                players[me].send(&result)?;
+            }
+        }
+
+        for (me, them) in [(0, 1), (1, 0)] {
+            let end = format!("END {} {}", players[me].total, players[them].total);
+            players[me].send(&end)?;
+        }
+
+        let [first, second] = players;
+        Ok(MatchSummary {
+            totals: [first.total, second.total],
+            names: [first.name, second.name],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn answers_bad_moves_with_an_error() {
+        let server = MatchServer::bind(0, 1, READ_TIMEOUT).unwrap();
+        let addr = server.local_addr().unwrap();
+        let handle = thread::spawn(move || server.run_match());
+
+        let mut streams = ["first", "second"].map(|name| {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            writeln!(stream, "HELLO {name}").unwrap();
+            let reader = BufReader::new(stream.try_clone().unwrap());
+            (stream, reader)
+        });
+
+        let mut transcript = Vec::new();
+        for (stream, reader) in streams.iter_mut() {
+            transcript.push(read_message(reader).unwrap());
+            transcript.push(read_message(reader).unwrap());
+            transcript.push(read_message(reader).unwrap());
+            writeln!(stream, "MOVE lizard").unwrap();
+            transcript.push(read_message(reader).unwrap());
+            writeln!(stream, "MOVE rock").unwrap();
+        }
+        for (_, reader) in streams.iter_mut() {
+            transcript.push(read_message(reader).unwrap());
+        }
+
+        assert_eq!(
+            transcript,
+            [
+                "WELCOME 1",
+                "START second 1",
+                "ROUND 1",
+                "ERROR Unrecognized move! (MOVE lizard)",
+                "WELCOME 2",
+                "START first 1",
+                "ROUND 1",
+                "ERROR Unrecognized move! (MOVE lizard)",
+                "RESULT rock rock draw 4 4 4",
+                "RESULT rock rock draw 4 4 4",
+            ]
+        );
+        assert_eq!(handle.join().unwrap().unwrap().totals, [4, 4]);
+    }
+
+    #[test]
+    fn aborts_matches_stalled_by_a_silent_player() {
+        let server = MatchServer::bind(0, 1, Duration::from_millis(50)).unwrap();
+        let addr = server.local_addr().unwrap();
+        let handle = thread::spawn(move || server.run_match());
+
+        let mut first = TcpStream::connect(addr).unwrap();
+        writeln!(first, "HELLO first").unwrap();
+        // connects, but never says hello
+        let _second = TcpStream::connect(addr).unwrap();
+
+        let error = handle.join().unwrap().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt;
+
+use crate::guide::{GuideRound, StrategyGuide};
+use crate::{evaluate_round, shape_for_outcome, Outcome, Shape};
+
+// how the second column of the strategy guide is read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpretation {
+    // part one: the second column is my move
+    Moves,
+    // part two: the second column is the outcome
+    Outcomes,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Tally {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub score: i32,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerTallies {
+    pub me: Tally,
+    pub opponent: Tally,
+}
+
+impl Tally {
+    fn record(&mut self, outcome: Outcome, score: i32) {
+        match outcome {
+            Outcome::Win => self.wins += 1,
+            Outcome::Draw => self.draws += 1,
+            Outcome::Loss => self.losses += 1,
+        }
+        self.score += score;
+    }
+}
+
+impl fmt::Display for Tally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} wins, {} draws, {} losses, {} points",
+            self.wins, self.draws, self.losses, self.score
+        )
+    }
+}
+
+fn opposite(outcome: Outcome) -> Outcome {
+    match outcome {
+        Outcome::Win => Outcome::Loss,
+        Outcome::Draw => Outcome::Draw,
+        Outcome::Loss => Outcome::Win,
+    }
+}
+
+fn my_move(round: &GuideRound, interpretation: Interpretation) -> Shape {
+    match interpretation {
+        Interpretation::Moves => round.my_move,
+        Interpretation::Outcomes => shape_for_outcome(round.opponent_move, round.outcome),
+    }
+}
+
+pub fn tally(guide: &StrategyGuide, interpretation: Interpretation) -> PlayerTallies {
+    guide
+        .rounds
+        .iter()
+        .fold(PlayerTallies::default(), |mut tallies, round| {
+            let scores = evaluate_round(round.opponent_move, my_move(round, interpretation));
+            tallies.me.record(scores.my_outcome, scores.my_score);
+            tallies
+                .opponent
+                .record(opposite(scores.my_outcome), scores.opponent_score);
+            tallies
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::guide::{parse_strategy, ParseMode};
+    use crate::STRATEGY;
+
+    #[test]
+    fn tallies_both_players() {
+        let guide = parse_strategy("A Y\nB X\nC Z", ParseMode::Strict).unwrap();
+
+        assert_eq!(
+            tally(&guide, Interpretation::Moves),
+            PlayerTallies {
+                me: Tally {
+                    wins: 1,
+                    draws: 1,
+                    losses: 1,
+                    score: 15
+                },
+                opponent: Tally {
+                    wins: 1,
+                    draws: 1,
+                    losses: 1,
+                    score: 15
+                },
+            }
+        );
+        assert_eq!(tally(&guide, Interpretation::Outcomes).opponent.score, 15);
+    }
+
+    #[test]
+    fn my_side_matches_the_puzzle_totals() {
+        let guide = parse_strategy(STRATEGY, ParseMode::Strict).unwrap();
+        let moves = tally(&guide, Interpretation::Moves);
+        let outcomes = tally(&guide, Interpretation::Outcomes);
+
+        assert_eq!(moves.me.score, 10595);
+        assert_eq!(outcomes.me.score, 9541);
+        assert_eq!(moves.me.wins, moves.opponent.losses);
+        assert_eq!(outcomes.me.draws, outcomes.opponent.draws);
+    }
+}
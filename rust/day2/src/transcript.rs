@@ -0,0 +1,165 @@
+use crate::guide::{parse_strategy, ParseError, ParseMode};
+use crate::{round_outcome, shape_for_outcome, Outcome, Shape};
+
+// how one interpretation of the second column scored a round
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scoring {
+    pub my_move: Shape,
+    pub outcome: Outcome,
+    pub shape_points: i32,
+    pub outcome_points: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundRecord {
+    pub line: usize,
+    pub opponent_move: Shape,
+    // part one: the second column is my move
+    pub as_move: Scoring,
+    // part two: the second column is the outcome
+    pub as_outcome: Scoring,
+}
+
+impl Scoring {
+    fn new(my_move: Shape, outcome: Outcome) -> Self {
+        Self {
+            my_move,
+            outcome,
+            shape_points: my_move as i32,
+            outcome_points: outcome as i32,
+        }
+    }
+
+    pub fn score(&self) -> i32 {
+        self.shape_points + self.outcome_points
+    }
+}
+
+pub fn transcript(strategy: &str) -> Result<Vec<RoundRecord>, ParseError> {
+    let guide = parse_strategy(strategy, ParseMode::Strict)?;
+
+    Ok(guide
+        .rounds
+        .iter()
+        .map(|round| RoundRecord {
+            line: round.line,
+            opponent_move: round.opponent_move,
+            as_move: Scoring::new(
+                round.my_move,
+                round_outcome(round.opponent_move, round.my_move),
+            ),
+            as_outcome: Scoring::new(
+                shape_for_outcome(round.opponent_move, round.outcome),
+                round.outcome,
+            ),
+        })
+        .collect())
+}
+
+pub fn to_csv(rounds: &[RoundRecord]) -> String {
+    let mut csv = String::from(
+        "line,opponent,\
+         move_shape,move_outcome,move_shape_points,move_outcome_points,move_score,\
+         outcome_shape,outcome_outcome,outcome_shape_points,outcome_outcome_points,outcome_score\n",
+    );
+
+    for round in rounds {
+        let columns = [round.as_move, round.as_outcome]
+            .iter()
+            .map(|s| {
+                format!(
+                    "{:?},{:?},{},{},{}",
+                    s.my_move,
+                    s.outcome,
+                    s.shape_points,
+                    s.outcome_points,
+                    s.score()
+                )
+            })
+            .collect::<Vec<_>>();
+        csv.push_str(&format!(
+            "{},{:?},{}\n",
+            round.line,
+            round.opponent_move,
+            columns.join(",")
+        ));
+    }
+
+    csv
+}
+
+fn scoring_to_json(scoring: &Scoring) -> String {
+    format!(
+        r#"{{"shape":"{:?}","outcome":"{:?}","shape_points":{},"outcome_points":{},"score":{}}}"#,
+        scoring.my_move,
+        scoring.outcome,
+        scoring.shape_points,
+        scoring.outcome_points,
+        scoring.score()
+    )
+}
+
+pub fn to_json(rounds: &[RoundRecord]) -> String {
+    let entries = rounds
+        .iter()
+        .map(|round| {
+            format!(
+                r#"{{"line":{},"opponent":"{:?}","as_move":{},"as_outcome":{}}}"#,
+                round.line,
+                round.opponent_move,
+                scoring_to_json(&round.as_move),
+                scoring_to_json(&round.as_outcome)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    format!(
+        r#"{{"rounds":[{}],"totals":{{"as_move":{},"as_outcome":{}}}}}"#,
+        entries.join(","),
+        rounds.iter().map(|r| r.as_move.score()).sum::<i32>(),
+        rounds.iter().map(|r| r.as_outcome.score()).sum::<i32>()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::STRATEGY;
+
+    #[test]
+    fn transcript_adds_up_to_the_puzzle_totals() {
+        let rounds = transcript(STRATEGY).unwrap();
+
+        assert_eq!(rounds.len(), STRATEGY.lines().count());
+        assert_eq!(rounds.iter().map(|r| r.as_move.score()).sum::<i32>(), 10595);
+        assert_eq!(
+            rounds.iter().map(|r| r.as_outcome.score()).sum::<i32>(),
+            9541
+        );
+    }
+
+    #[test]
+    fn exports_csv_rows() {
+        let csv = to_csv(&transcript("A Y\nB X\nC Z").unwrap());
+        let lines = csv.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("line,opponent,move_shape"));
+        assert_eq!(lines[1], "1,Rock,Paper,Win,2,6,8,Rock,Draw,1,3,4");
+        assert_eq!(lines[2], "2,Paper,Rock,Loss,1,0,1,Rock,Loss,1,0,1");
+        assert_eq!(lines[3], "3,Scissors,Scissors,Draw,3,3,6,Rock,Win,1,6,7");
+    }
+
+    #[test]
+    fn exports_json_with_totals() {
+        let json = to_json(&transcript("A Y").unwrap());
+
+        assert_eq!(
+            json,
+            r#"{"rounds":[{"line":1,"opponent":"Rock","#.to_owned()
+                + r#""as_move":{"shape":"Paper","outcome":"Win","shape_points":2,"outcome_points":6,"score":8},"#
+                + r#""as_outcome":{"shape":"Rock","outcome":"Draw","shape_points":1,"outcome_points":3,"score":4}}],"#
+                + r#""totals":{"as_move":8,"as_outcome":4}}"#
+        );
+    }
+}
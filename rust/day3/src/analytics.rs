@@ -0,0 +1,232 @@
+use std::cmp::Reverse;
+use std::ops::RangeInclusive;
+
+use crate::item_set::{item_type_for_priority, ItemSet};
+use crate::{check_items, find_common_items, priority_for_item_type};
+use crate::{split_compartments, split_groups};
+use crate::{LayoutError, COMPARTMENTS, GROUP_SIZE};
+
+const PRIORITY_BUCKET: u8 = 13;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemStats {
+    pub item: char,
+    pub priority: u8,
+    // every copy of the item, across all rucksacks
+    pub occurrences: usize,
+    // rucksacks holding at least one copy
+    pub rucksacks: usize,
+    // rucksacks where both compartments hold it
+    pub shared: usize,
+    // groups where it is the badge
+    pub badges: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriorityBucket {
+    pub priorities: RangeInclusive<u8>,
+    pub shared: usize,
+    pub badges: usize,
+}
+
+// one entry per item type, in priority order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analytics {
+    pub items: Vec<ItemStats>,
+}
+
+pub fn analyze(rucksacks: &str) -> Result<Analytics, LayoutError> {
+    let mut items = (1..=52)
+        .map(|priority| ItemStats {
+            item: item_type_for_priority(priority),
+            priority,
+            occurrences: 0,
+            rucksacks: 0,
+            shared: 0,
+            badges: 0,
+        })
+        .collect::<Vec<_>>();
+
+    let lines = rucksacks.lines().collect::<Vec<_>>();
+    for (index, rucksack) in lines.iter().enumerate() {
+        check_items(index + 1, rucksack)?;
+        for item in rucksack.chars() {
+            items[priority_index(item)].occurrences += 1;
+        }
+        for item in ItemSet::from_items(rucksack).iter() {
+            items[priority_index(item)].rucksacks += 1;
+        }
+
+        let compartments = split_compartments(index + 1, rucksack, COMPARTMENTS)?;
+        for item in find_common_items(&compartments).iter() {
+            items[priority_index(item)].shared += 1;
+        }
+    }
+
+    for group in split_groups(&lines, GROUP_SIZE)? {
+        for item in find_common_items(&group).iter() {
+            items[priority_index(item)].badges += 1;
+        }
+    }
+
+    Ok(Analytics { items })
+}
+
+fn priority_index(item: char) -> usize {
+    priority_for_item_type(item) as usize - 1
+}
+
+impl Analytics {
+    // most shared first, ties in priority order
+    pub fn most_common_shared(&self, count: usize) -> Vec<ItemStats> {
+        let mut items = self
+            .items
+            .iter()
+            .filter(|s| s.shared > 0)
+            .copied()
+            .collect::<Vec<_>>();
+        items.sort_by_key(|s| Reverse(s.shared));
+        items.truncate(count);
+        items
+    }
+
+    pub fn priority_distribution(&self) -> Vec<PriorityBucket> {
+        self.items
+            .chunks(PRIORITY_BUCKET as usize)
+            .map(|chunk| PriorityBucket {
+                priorities: chunk[0].priority..=chunk[chunk.len() - 1].priority,
+                shared: chunk.iter().map(|s| s.shared).sum(),
+                badges: chunk.iter().map(|s| s.badges).sum(),
+            })
+            .collect()
+    }
+
+    pub fn to_table(&self) -> String {
+        let mut table = String::from("item  priority  occurrences  rucksacks  shared  badges\n");
+        for s in &self.items {
+            table.push_str(&format!(
+                "{:<4}  {:>8}  {:>11}  {:>9}  {:>6}  {:>6}\n",
+                s.item, s.priority, s.occurrences, s.rucksacks, s.shared, s.badges
+            ));
+        }
+
+        table.push_str("\nmost common shared items\n");
+        for s in self.most_common_shared(5) {
+            table.push_str(&format!("{:<4}  {:>6}\n", s.item, s.shared));
+        }
+
+        table.push_str("\npriorities  shared  badges\n");
+        for bucket in self.priority_distribution() {
+            let priorities = format!("{}-{}", bucket.priorities.start(), bucket.priorities.end());
+            table.push_str(&format!(
+                "{priorities:<10}  {:>6}  {:>6}\n",
+                bucket.shared, bucket.badges
+            ));
+        }
+
+        table
+    }
+
+    pub fn to_json(&self) -> String {
+        let items = self
+            .items
+            .iter()
+            .map(|s| {
+                format!(
+                    r#"{{"item":"{}","priority":{},"occurrences":{},"rucksacks":{},"shared":{},"badges":{}}}"#,
+                    s.item, s.priority, s.occurrences, s.rucksacks, s.shared, s.badges
+                )
+            })
+            .collect::<Vec<_>>();
+        let most_common_shared = self
+            .most_common_shared(5)
+            .iter()
+            .map(|s| format!(r#""{}""#, s.item))
+            .collect::<Vec<_>>();
+        let distribution = self
+            .priority_distribution()
+            .iter()
+            .map(|b| {
+                format!(
+                    r#"{{"from":{},"to":{},"shared":{},"badges":{}}}"#,
+                    b.priorities.start(),
+                    b.priorities.end(),
+                    b.shared,
+                    b.badges
+                )
+            })
+            .collect::<Vec<_>>();
+
+        format!(
+            r#"{{"items":[{}],"most_common_shared":[{}],"priority_distribution":[{}]}}"#,
+            items.join(","),
+            most_common_shared.join(","),
+            distribution.join(",")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RUCKSACKS;
+
+    #[test]
+    fn counts_items_shared_items_and_badges() {
+        let analytics = analyze("aabacd\nbBbBZa\nAaqa").unwrap();
+        let stats = |item| analytics.items[priority_index(item)];
+
+        assert_eq!(stats('a').occurrences, 6);
+        assert_eq!(stats('a').rucksacks, 3);
+        assert_eq!(stats('a').shared, 2);
+        assert_eq!(stats('a').badges, 1);
+        assert_eq!(stats('B').shared, 1);
+        assert_eq!(stats('q').occurrences, 1);
+        assert_eq!(
+            analytics
+                .most_common_shared(2)
+                .iter()
+                .map(|s| s.item)
+                .collect::<String>(),
+            "aB"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_items() {
+        assert_eq!(
+            analyze("abab\nab ab\ncdcd"),
+            Err(LayoutError::UnknownItem { line: 2, item: ' ' })
+        );
+    }
+
+    #[test]
+    fn distribution_adds_up_to_the_puzzle() {
+        let analytics = analyze(RUCKSACKS).unwrap();
+        let distribution = analytics.priority_distribution();
+
+        assert_eq!(distribution.len(), 4);
+        assert_eq!(distribution[3].priorities, 40..=52);
+        assert_eq!(distribution.iter().map(|b| b.shared).sum::<usize>(), 300);
+        assert_eq!(distribution.iter().map(|b| b.badges).sum::<usize>(), 100);
+        assert_eq!(
+            analytics
+                .items
+                .iter()
+                .map(|s| s.priority as usize * s.shared)
+                .sum::<usize>(),
+            7428
+        );
+    }
+
+    #[test]
+    fn exports_json() {
+        let json = analyze("abca\nbb\ncc").unwrap().to_json();
+
+        assert!(json.starts_with(
+            r#"{"items":[{"item":"a","priority":1,"occurrences":2,"rucksacks":1,"shared":1,"badges":0},"#
+        ));
+        assert!(json.contains(r#""most_common_shared":["a","b","c"]"#));
+        assert!(json.ends_with(r#"{"from":40,"to":52,"shared":0,"badges":0}]}"#));
+    }
+}
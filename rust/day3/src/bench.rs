@@ -0,0 +1,74 @@
+use std::collections::HashSet;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::{find_common_item, find_common_items, sum_of_group_priorities, sum_of_priorities};
+use crate::{priority_for_item_type, RUCKSACKS};
+
+const ITERATIONS: u32 = 1000;
+
+// the `HashSet<char>` implementation `ItemSet` replaced, kept as the baseline to compare against
+fn find_common_item_with_hash_sets(buckets: &[&str]) -> char {
+    buckets
+        .iter()
+        .map(|b| b.chars().collect::<HashSet<_>>())
+        .reduce(|acc, set| &acc & &set)
+        .and_then(|set| set.into_iter().next())
+        .unwrap_or_else(|| panic!("No common item found!"))
+}
+
+fn sum_with(find: fn(&[&str]) -> char) -> (u32, u32) {
+    let rucksacks = RUCKSACKS.lines().collect::<Vec<_>>();
+
+    let compartments = rucksacks
+        .iter()
+        .map(|r| {
+            let (first, second) = r.split_at(r.len() / 2);
+            priority_for_item_type(find(&[first, second])) as u32
+        })
+        .sum();
+    let groups = rucksacks
+        .chunks(3)
+        .map(|group| priority_for_item_type(find(group)) as u32)
+        .sum();
+
+    (compartments, groups)
+}
+
+fn time(find: fn(&[&str]) -> char) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(sum_with(black_box(find)));
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn has_single_common_item(buckets: &[&str]) -> bool {
+    find_common_items(buckets).len() == 1
+}
+
+pub fn run() {
+    // the baseline picks an arbitrary item when several are shared,
+    // so the sums are only comparable when every bucket has exactly one
+    let rucksacks = RUCKSACKS.lines().collect::<Vec<_>>();
+    assert!(rucksacks
+        .iter()
+        .all(|r| has_single_common_item(&[&r[..r.len() / 2], &r[r.len() / 2..]])));
+    assert!(rucksacks.chunks(3).all(has_single_common_item));
+
+    // both implementations must agree before their timings mean anything
+    assert_eq!(
+        sum_with(find_common_item_with_hash_sets),
+        (sum_of_priorities(), sum_of_group_priorities())
+    );
+
+    let hash_sets = time(find_common_item_with_hash_sets);
+    let item_sets = time(find_common_item);
+
+    println!("HashSet<char>: {hash_sets:?} per run");
+    println!("ItemSet:       {item_sets:?} per run");
+    println!(
+        "Speed-up:      {:.1}x",
+        hash_sets.as_secs_f64() / item_sets.as_secs_f64()
+    );
+}
@@ -0,0 +1,190 @@
+use std::{fs::File, io, path::Path};
+
+use memmap2::Mmap;
+
+use crate::item_set::ItemSet;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ByteInputError {
+    // anything but an ASCII letter or a line ending, 1-based line and column
+    UnsupportedByte {
+        line: usize,
+        column: usize,
+        byte: u8,
+    },
+    UnevenRucksack {
+        line: usize,
+    },
+    // line of the first rucksack of the incomplete group
+    PartialGroup {
+        line: usize,
+    },
+    // the rucksack on this line, or the group starting on it, shares nothing
+    NoCommonItem {
+        line: usize,
+    },
+}
+
+// rucksacks with their line number, split like `str::lines` so a blank line is an empty
+// rucksack, and a final line ending doesn't start another one
+fn rucksacks(input: &[u8]) -> impl Iterator<Item = (usize, &[u8])> {
+    input
+        .split_inclusive(|&b| b == b'\n')
+        .map(|line| match line.strip_suffix(b"\n") {
+            Some(line) => line.strip_suffix(b"\r").unwrap_or(line),
+            None => line,
+        })
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+}
+
+// one pass over the whole input, so the per-rucksack loops can trust every byte
+pub fn validate(input: &[u8]) -> Result<(), ByteInputError> {
+    for (line, items) in rucksacks(input) {
+        if let Some(column) = items.iter().position(|b| !b.is_ascii_alphabetic()) {
+            return Err(ByteInputError::UnsupportedByte {
+                line,
+                column: column + 1,
+                byte: items[column],
+            });
+        }
+        if items.len() % 2 != 0 {
+            return Err(ByteInputError::UnevenRucksack { line });
+        }
+    }
+
+    Ok(())
+}
+
+fn common_priority(line: usize, buckets: &[&[u8]]) -> Result<u64, ByteInputError> {
+    let common = buckets
+        .iter()
+        .map(|b| ItemSet::from_bytes(b))
+        .reduce(|acc, set| acc & set)
+        .unwrap_or_default();
+
+    common
+        .lowest_priority()
+        .map(u64::from)
+        .ok_or(ByteInputError::NoCommonItem { line })
+}
+
+// part one
+pub fn sum_of_priorities(input: &[u8]) -> Result<u64, ByteInputError> {
+    validate(input)?;
+
+    rucksacks(input)
+        .map(|(line, rucksack)| {
+            let (first, second) = rucksack.split_at(rucksack.len() / 2);
+            common_priority(line, &[first, second])
+        })
+        .sum()
+}
+
+// part two
+pub fn sum_of_group_priorities(input: &[u8]) -> Result<u64, ByteInputError> {
+    validate(input)?;
+
+    let mut group: [&[u8]; 3] = [&[]; 3];
+    let (mut filled, mut first_line) = (0, 0);
+    let mut sum = 0;
+
+    for (line, rucksack) in rucksacks(input) {
+        if filled == 0 {
+            first_line = line;
+        }
+        group[filled] = rucksack;
+        filled += 1;
+
+        if filled == group.len() {
+            sum += common_priority(first_line, &group)?;
+            filled = 0;
+        }
+    }
+
+    if filled > 0 {
+        return Err(ByteInputError::PartialGroup { line: first_line });
+    }
+
+    Ok(sum)
+}
+
+// the mapping must outlive the slices borrowed from it, so callers keep it around
+pub fn map_file(path: impl AsRef<Path>) -> io::Result<Mmap> {
+    let file = File::open(path)?;
+    // SAFETY: the input is only read, and files being rewritten while we run aren't supported
+    unsafe { Mmap::map(&file) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RUCKSACKS;
+
+    #[test]
+    fn matches_the_str_implementation() {
+        assert_eq!(sum_of_priorities(RUCKSACKS.as_bytes()), Ok(7428));
+        assert_eq!(sum_of_group_priorities(RUCKSACKS.as_bytes()), Ok(2650));
+    }
+
+    #[test]
+    fn rejects_non_ascii_and_malformed_input() {
+        assert_eq!(
+            sum_of_priorities("abab\nabéa".as_bytes()),
+            Err(ByteInputError::UnsupportedByte {
+                line: 2,
+                column: 3,
+                byte: 0xc3
+            })
+        );
+        assert_eq!(
+            sum_of_priorities(b"abab\r\nabc\r\n"),
+            Err(ByteInputError::UnevenRucksack { line: 2 })
+        );
+        assert_eq!(
+            sum_of_group_priorities(b"aa\naa\naa\n\naa\n"),
+            Err(ByteInputError::PartialGroup { line: 4 })
+        );
+        assert_eq!(
+            sum_of_priorities(b"abcd\nabab"),
+            Err(ByteInputError::NoCommonItem { line: 1 })
+        );
+        assert_eq!(
+            sum_of_group_priorities(b"ab\nab\nab\nab\ncd\nab"),
+            Err(ByteInputError::NoCommonItem { line: 4 })
+        );
+    }
+
+    #[test]
+    fn splits_lines_like_the_str_implementation() {
+        // a blank rucksack shares nothing, on either path
+        assert_eq!(
+            sum_of_priorities(b"abab\n\nabab\n"),
+            Err(ByteInputError::NoCommonItem { line: 2 })
+        );
+        assert_eq!(
+            crate::sum_of_compartment_priorities("abab\n\nabab\n", 2),
+            Err(crate::LayoutError::NoCommonItem { line: 2 })
+        );
+        assert_eq!(sum_of_priorities(b"abab\r\naDbD\r\n"), Ok(1 + 30));
+        assert_eq!(sum_of_group_priorities(b""), Ok(0));
+    }
+
+    #[test]
+    fn processes_huge_memory_mapped_inputs() {
+        let path = std::env::temp_dir().join(format!("day3-{}.txt", std::process::id()));
+        let copies = 200;
+        let mut input = String::with_capacity((RUCKSACKS.len() + 2) * copies);
+        for _ in 0..copies {
+            input.push_str(&RUCKSACKS.replace('\n', "\r\n"));
+            input.push_str("\r\n");
+        }
+        std::fs::write(&path, input).unwrap();
+
+        let mapped = map_file(&path).unwrap();
+        let result = (sum_of_priorities(&mapped), sum_of_group_priorities(&mapped));
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(result, (Ok(7428 * copies as u64), Ok(2650 * copies as u64)));
+    }
+}
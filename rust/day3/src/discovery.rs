@@ -0,0 +1,191 @@
+use crate::item_set::ItemSet;
+use crate::{check_items, find_common_item, find_common_items, priority_for_item_type};
+use crate::{LayoutError, GROUP_SIZE};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadgeGroup {
+    // line numbers of the rucksacks in the group, ascending
+    pub lines: [usize; GROUP_SIZE],
+    pub badge: char,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Discovery {
+    pub partitions: Vec<Vec<BadgeGroup>>,
+    // the search covered every possibility, so no partitions means none exists
+    pub exhaustive: bool,
+}
+
+impl Discovery {
+    // summed like `sum_of_badge_priorities`
+    pub fn badge_priorities(partition: &[BadgeGroup]) -> u32 {
+        partition
+            .iter()
+            .map(|group| priority_for_item_type(group.badge) as u32)
+            .sum()
+    }
+}
+
+struct Search<'a> {
+    rucksacks: Vec<&'a str>,
+    sets: Vec<ItemSet>,
+    assigned: Vec<bool>,
+    groups: Vec<BadgeGroup>,
+    limit: usize,
+    discovery: Discovery,
+}
+
+impl Search<'_> {
+    // always groups the first unassigned rucksack, so every partition is found exactly once;
+    // only a partition past the limit makes the search incomplete, and ends it
+    fn run(&mut self) {
+        let Some(first) = self.assigned.iter().position(|a| !a) else {
+            if self.discovery.partitions.len() == self.limit {
+                self.discovery.exhaustive = false;
+            } else {
+                self.discovery.partitions.push(self.groups.clone());
+            }
+            return;
+        };
+        self.assigned[first] = true;
+
+        for second in first + 1..self.rucksacks.len() {
+            if !self.discovery.exhaustive {
+                break;
+            }
+            if self.assigned[second] || (self.sets[first] & self.sets[second]).len() == 0 {
+                continue;
+            }
+            self.assigned[second] = true;
+
+            for third in second + 1..self.rucksacks.len() {
+                let group = [
+                    self.rucksacks[first],
+                    self.rucksacks[second],
+                    self.rucksacks[third],
+                ];
+                if !self.discovery.exhaustive {
+                    break;
+                }
+                if self.assigned[third] || find_common_items(&group).len() != 1 {
+                    continue;
+                }
+
+                self.assigned[third] = true;
+                self.groups.push(BadgeGroup {
+                    lines: [first + 1, second + 1, third + 1],
+                    badge: find_common_item(&group),
+                });
+                self.run();
+                self.groups.pop();
+                self.assigned[third] = false;
+            }
+
+            self.assigned[second] = false;
+        }
+
+        self.assigned[first] = false;
+    }
+}
+
+// partitions rucksacks listed in any order into groups of three sharing exactly one badge,
+// stopping after `limit` partitions
+pub fn discover_groups(rucksacks: &str, limit: usize) -> Result<Discovery, LayoutError> {
+    let rucksacks = rucksacks.lines().collect::<Vec<_>>();
+    for (index, rucksack) in rucksacks.iter().enumerate() {
+        check_items(index + 1, rucksack)?;
+    }
+
+    if rucksacks.len() % GROUP_SIZE != 0 {
+        return Ok(Discovery {
+            partitions: vec![],
+            exhaustive: true,
+        });
+    }
+
+    let mut search = Search {
+        sets: rucksacks.iter().map(|r| ItemSet::from_items(r)).collect(),
+        assigned: vec![false; rucksacks.len()],
+        groups: vec![],
+        rucksacks,
+        limit,
+        discovery: Discovery {
+            partitions: vec![],
+            exhaustive: true,
+        },
+    };
+    search.run();
+    Ok(search.discovery)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_every_partition() {
+        // 1, 3 and 4 only share c, but that would leave 2, 5 and 6 without a badge
+        let discovery = discover_groups("ac\nax\nbcy\nbcz\nad\nbw", 10).unwrap();
+
+        assert!(discovery.exhaustive);
+        assert_eq!(
+            discovery.partitions,
+            [vec![
+                BadgeGroup {
+                    lines: [1, 2, 5],
+                    badge: 'a'
+                },
+                BadgeGroup {
+                    lines: [3, 4, 6],
+                    badge: 'b'
+                },
+            ]]
+        );
+        assert_eq!(Discovery::badge_priorities(&discovery.partitions[0]), 3);
+        // the same rucksacks, listed group by group
+        assert_eq!(
+            crate::sum_of_badge_priorities("ac\nax\nad\nbcy\nbcz\nbw", GROUP_SIZE),
+            Ok(3)
+        );
+    }
+
+    #[test]
+    fn stays_exhaustive_when_the_limit_is_met_exactly() {
+        let discovery = discover_groups("ac\nax\nbcy\nbcz\nad\nbw", 1).unwrap();
+
+        assert!(discovery.exhaustive);
+        assert_eq!(discovery.partitions.len(), 1);
+
+        let stopped = discover_groups("ac\nax\nbcy\nbcz\nad\nbw", 0).unwrap();
+        assert!(!stopped.exhaustive);
+        assert!(stopped.partitions.is_empty());
+    }
+
+    #[test]
+    fn proves_when_no_partition_exists() {
+        assert_eq!(
+            discover_groups("ab\nab\nab", 10),
+            Ok(Discovery {
+                partitions: vec![],
+                exhaustive: true
+            })
+        );
+        assert!(discover_groups("a\na", 10).unwrap().partitions.is_empty());
+        assert_eq!(
+            discover_groups("ab\nab\na-b", 10),
+            Err(LayoutError::UnknownItem { line: 3, item: '-' })
+        );
+    }
+
+    #[test]
+    fn stops_at_the_limit() {
+        let discovery = discover_groups("a\na\na\na\na\na", 2).unwrap();
+
+        // the six rucksacks can be split in ten ways
+        assert_eq!(discovery.partitions.len(), 2);
+        assert!(!discovery.exhaustive);
+        let all = discover_groups("a\na\na\na\na\na", 20).unwrap();
+        assert_eq!(all.partitions.len(), 10);
+        assert!(discover_groups("a\na\na\na\na\na", 10).unwrap().exhaustive);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::item_set::item_type_for_priority;
+use crate::priority_for_item_type;
+
+// SplitMix64, good enough to shuffle letters and reproducible from a seed
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // in `0..bound`
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedInput {
+    pub rucksacks: String,
+    // the item shared by the compartments of each rucksack
+    pub shared_items: Vec<char>,
+    // the badge of each group of three
+    pub badges: Vec<char>,
+    pub sum_of_priorities: u32,
+    pub sum_of_group_priorities: u32,
+}
+
+const MAX_COMPARTMENT_SIZE: usize = 16;
+
+fn fill_compartment(rng: &mut Rng, required: &[char], pool: &[char], size: usize) -> Vec<char> {
+    let mut items = required.to_vec();
+    while items.len() < size {
+        items.push(pool[rng.below(pool.len())]);
+    }
+    rng.shuffle(&mut items);
+    items
+}
+
+// Every rucksack of a group draws its other items from its own third of the alphabet, so
+// the badge is the only item all three can share; each third is further split between the
+// two compartments, so the shared item is the only one both can hold.
+fn generate_group(
+    rng: &mut Rng,
+    rucksacks: &mut Vec<String>,
+    shared_items: &mut Vec<char>,
+) -> char {
+    let mut letters = (1..=52).map(item_type_for_priority).collect::<Vec<_>>();
+    rng.shuffle(&mut letters);
+    let badge = letters.pop().unwrap();
+
+    for pool in letters.chunks(letters.len() / 3) {
+        let (shared, rest) = pool.split_first().unwrap();
+        let (first_pool, second_pool) = rest.split_at(rest.len() / 2);
+
+        let size = 2 + rng.below(MAX_COMPARTMENT_SIZE - 1);
+        let (first_required, second_required) = if rng.below(2) == 0 {
+            (vec![*shared, badge], vec![*shared])
+        } else {
+            (vec![*shared], vec![*shared, badge])
+        };
+
+        let mut rucksack = fill_compartment(rng, &first_required, first_pool, size);
+        rucksack.extend(fill_compartment(rng, &second_required, second_pool, size));
+        rucksacks.push(rucksack.into_iter().collect());
+        shared_items.push(*shared);
+    }
+
+    badge
+}
+
+pub fn generate(seed: u64, groups: usize) -> GeneratedInput {
+    let mut rng = Rng::new(seed);
+    let mut rucksacks = Vec::with_capacity(groups * 3);
+    let mut shared_items = Vec::with_capacity(groups * 3);
+
+    let badges = (0..groups)
+        .map(|_| generate_group(&mut rng, &mut rucksacks, &mut shared_items))
+        .collect::<Vec<_>>();
+
+    let priorities = |items: &[char]| {
+        items
+            .iter()
+            .map(|&item| priority_for_item_type(item) as u32)
+            .sum()
+    };
+
+    GeneratedInput {
+        rucksacks: rucksacks.join("\n"),
+        sum_of_priorities: priorities(&shared_items),
+        sum_of_group_priorities: priorities(&badges),
+        shared_items,
+        badges,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::validate;
+    use crate::{find_common_item, sum_of_badge_priorities, sum_of_compartment_priorities};
+    use crate::{COMPARTMENTS, GROUP_SIZE};
+
+    #[test]
+    fn is_reproducible_from_the_seed() {
+        assert_eq!(generate(42, 5), generate(42, 5));
+        assert_ne!(generate(42, 5).rucksacks, generate(43, 5).rucksacks);
+        assert_eq!(generate(7, 4).rucksacks.lines().count(), 12);
+    }
+
+    #[test]
+    fn every_generated_input_has_unique_shared_items_and_badges() {
+        for seed in 0..200 {
+            let generated = generate(seed, 10);
+
+            assert_eq!(validate(&generated.rucksacks), Ok(vec![]), "seed {seed}");
+            for (rucksack, &shared) in generated.rucksacks.lines().zip(&generated.shared_items) {
+                let (first, second) = rucksack.split_at(rucksack.len() / 2);
+                assert_eq!(find_common_item(&[first, second]), shared, "seed {seed}");
+            }
+        }
+    }
+
+    #[test]
+    fn sums_match_the_expected_answers() {
+        for seed in 0..200 {
+            let generated = generate(seed, 10);
+
+            assert_eq!(
+                sum_of_compartment_priorities(&generated.rucksacks, COMPARTMENTS),
+                Ok(generated.sum_of_priorities)
+            );
+            assert_eq!(
+                sum_of_badge_priorities(&generated.rucksacks, GROUP_SIZE),
+                Ok(generated.sum_of_group_priorities)
+            );
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::ops::{BitAnd, BitOr};
+
+use crate::priority_for_item_type;
+
+// one bit per item type, bit n set for the item with priority n
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+pub fn item_type_for_priority(priority: u8) -> char {
+    match priority {
+        1..=26 => (priority + 96) as char,
+        27..=52 => (priority + 38) as char,
+        _ => panic!("Unsupported priority! ({priority})"),
+    }
+}
+
+impl ItemSet {
+    pub fn from_items(items: &str) -> Self {
+        items.chars().collect()
+    }
+
+    // expects ASCII letters only, see `bytes::validate` to check that up front
+    pub fn from_bytes(items: &[u8]) -> Self {
+        Self(items.iter().fold(0, |set, &item| match item {
+            b'a'..=b'z' => set | 1 << (item - 96),
+            _ => set | 1 << (item - 38),
+        }))
+    }
+
+    pub fn insert(&mut self, item_type: char) {
+        self.0 |= 1 << priority_for_item_type(item_type);
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn lowest_priority(&self) -> Option<u8> {
+        (self.0 != 0).then(|| self.0.trailing_zeros() as u8)
+    }
+
+    // lowest priority first
+    pub fn iter(&self) -> impl Iterator<Item = char> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let priority = bits.trailing_zeros() as u8;
+            // clears the lowest set bit
+            bits &= bits - 1;
+            Some(item_type_for_priority(priority))
+        })
+    }
+}
+
+impl FromIterator<char> for ItemSet {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        let mut set = Self::default();
+        for item_type in iter {
+            set.insert(item_type);
+        }
+        set
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        self.intersection(rhs)
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersects_and_unites_sets() {
+        let a = ItemSet::from_items("abcZ");
+        let b = ItemSet::from_items("Zcxc");
+
+        assert_eq!((a & b).iter().collect::<String>(), "cZ");
+        assert_eq!((a | b).iter().collect::<String>(), "abcxZ");
+        assert_eq!((a | b).len(), 5);
+        assert_eq!((a & ItemSet::from_items("q")).len(), 0);
+    }
+
+    #[test]
+    fn iterates_in_priority_order() {
+        let set = ItemSet::from_items("ZzAa");
+
+        assert_eq!(set.iter().collect::<String>(), "azAZ");
+    }
+
+    #[test]
+    fn maps_priorities_back_to_item_types() {
+        for item_type in ('a'..='z').chain('A'..='Z') {
+            let priority = priority_for_item_type(item_type);
+            assert_eq!(item_type_for_priority(priority), item_type);
+        }
+    }
+}
@@ -1,5 +1,23 @@
-use std::{char, collections::HashSet};
+use std::char;
 
+use item_set::ItemSet;
+use priority_table::PriorityTable;
+use repacking::RepackingError;
+
+mod analytics;
+mod bench;
+mod bytes;
+mod discovery;
+mod generator;
+mod item_set;
+mod priority_table;
+mod repacking;
+mod validation;
+
+const COMPARTMENTS: usize = 2;
+const GROUP_SIZE: usize = 3;
+
+// the puzzle's letters only, `PriorityTable::default` holds the same mapping
 fn priority_for_item_type(item_type: char) -> u8 {
     match item_type {
         'A'..='Z' => item_type as u8 - 38,
@@ -8,49 +26,288 @@ fn priority_for_item_type(item_type: char) -> u8 {
     }
 }
 
-fn find_common_item(buckets: &[&str]) -> char {
+// every item shared by all buckets, in priority order
+fn find_common_items(buckets: &[&str]) -> ItemSet {
     buckets
         .iter()
-        .map(|b| b.chars().collect::<HashSet<_>>())
-        .reduce(|acc, set| &acc & &set)
-        .and_then(|set| set.into_iter().next())
+        .map(|b| ItemSet::from_items(b))
+        .reduce(|acc, set| acc & set)
+        .unwrap_or_default()
+}
+
+// picks the lowest priority item when several are shared, see `validation` to detect that
+fn find_common_item(buckets: &[&str]) -> char {
+    find_common_items(buckets)
+        .iter()
+        .next()
         .unwrap_or_else(|| panic!("No common item found!"))
 }
 
-// part one
-fn sum_of_priorities() -> i16 {
-    RUCKSACKS
+#[derive(Debug, PartialEq, Eq)]
+enum LayoutError {
+    UnevenCompartments {
+        line: usize,
+        items: usize,
+        compartments: usize,
+    },
+    PartialGroup {
+        first_line: usize,
+        rucksacks: usize,
+        group_size: usize,
+    },
+    // the rucksack on this line, or the group starting on it, shares nothing
+    NoCommonItem {
+        line: usize,
+    },
+    // an item without a priority on this line
+    UnknownItem {
+        line: usize,
+        item: char,
+    },
+}
+
+fn split_compartments(
+    line: usize,
+    rucksack: &str,
+    compartments: usize,
+) -> Result<Vec<&str>, LayoutError> {
+    let items = rucksack.chars().count();
+    if compartments == 0 || !items.is_multiple_of(compartments) {
+        return Err(LayoutError::UnevenCompartments {
+            line,
+            items,
+            compartments,
+        });
+    }
+
+    // by character, so items outside ASCII stay whole
+    let bounds = rucksack
+        .char_indices()
+        .map(|(index, _)| index)
+        .chain([rucksack.len()])
+        .collect::<Vec<_>>();
+    let size = items / compartments;
+    Ok((0..compartments)
+        .map(|i| &rucksack[bounds[i * size]..bounds[(i + 1) * size]])
+        .collect())
+}
+
+fn split_groups<'a>(
+    rucksacks: &[&'a str],
+    group_size: usize,
+) -> Result<Vec<Vec<&'a str>>, LayoutError> {
+    if group_size == 0 || !rucksacks.len().is_multiple_of(group_size) {
+        let rucksacks_left = rucksacks
+            .len()
+            .checked_rem(group_size)
+            .unwrap_or(rucksacks.len());
+        return Err(LayoutError::PartialGroup {
+            first_line: rucksacks.len() - rucksacks_left + 1,
+            rucksacks: rucksacks_left,
+            group_size,
+        });
+    }
+
+    Ok(rucksacks.chunks(group_size).map(<[_]>::to_vec).collect())
+}
+
+// `ItemSet` only holds the puzzle's letters
+fn check_items(line: usize, rucksack: &str) -> Result<(), LayoutError> {
+    match rucksack.chars().find(|item| !item.is_ascii_alphabetic()) {
+        Some(item) => Err(LayoutError::UnknownItem { line, item }),
+        None => Ok(()),
+    }
+}
+
+// like `find_common_item`, without giving up when nothing is shared
+fn common_item_priority(line: usize, buckets: &[&str]) -> Result<u32, LayoutError> {
+    find_common_items(buckets)
+        .iter()
+        .next()
+        .map(|item| priority_for_item_type(item) as u32)
+        .ok_or(LayoutError::NoCommonItem { line })
+}
+
+fn sum_of_compartment_priorities(rucksacks: &str, compartments: usize) -> Result<u32, LayoutError> {
+    rucksacks
         .lines()
-        .map(|rucksack| {
-            // assume even number of items in rucksack
-            let half = rucksack.len() / 2;
-            let first_compartment = &rucksack[..half];
-            let second_compartment = &rucksack[half..];
-
-            let item = find_common_item(&[first_compartment, second_compartment]);
-            priority_for_item_type(item) as i16
+        .enumerate()
+        .map(|(index, rucksack)| {
+            check_items(index + 1, rucksack)?;
+            let buckets = split_compartments(index + 1, rucksack, compartments)?;
+            common_item_priority(index + 1, &buckets)
         })
         .sum()
 }
 
-// part two
-fn sum_of_group_priorities() -> i16 {
-    RUCKSACKS
-        .lines()
-        .collect::<Vec<_>>()
-        .chunks(3)
-        .map(|group| {
-            let group_key = find_common_item(group);
-            priority_for_item_type(group_key) as i16
-        })
+fn sum_of_badge_priorities(rucksacks: &str, group_size: usize) -> Result<u32, LayoutError> {
+    let lines = rucksacks.lines().collect::<Vec<_>>();
+    for (index, rucksack) in lines.iter().enumerate() {
+        check_items(index + 1, rucksack)?;
+    }
+
+    split_groups(&lines, group_size)?
+        .iter()
+        .enumerate()
+        .map(|(index, group)| common_item_priority(index * group_size + 1, group))
         .sum()
 }
 
+// part one
+fn sum_of_priorities() -> u32 {
+    sum_of_compartment_priorities(RUCKSACKS, COMPARTMENTS).expect("Uneven rucksack!")
+}
+
+// part two
+fn sum_of_group_priorities() -> u32 {
+    sum_of_badge_priorities(RUCKSACKS, GROUP_SIZE).expect("Partial group!")
+}
+
 fn main() {
-    println!("Result: {}", sum_of_priorities());
-    println!("Group: {}", sum_of_group_priorities());
+    match std::env::args().nth(1).as_deref() {
+        Some("analytics") => match analytics::analyze(RUCKSACKS) {
+            Ok(analytics) if std::env::args().nth(2).as_deref() == Some("json") => {
+                println!("{}", analytics.to_json())
+            }
+            Ok(analytics) => print!("{}", analytics.to_table()),
+            Err(error) => println!("{error:?}"),
+        },
+        Some("bench") => bench::run(),
+        Some("bytes") => {
+            let mapped = std::env::args().nth(2).map(bytes::map_file).transpose();
+            let mapped = mapped.expect("Cannot map input file!");
+            let input = mapped.as_deref().unwrap_or(RUCKSACKS.as_bytes());
+
+            match bytes::sum_of_priorities(input) {
+                Ok(sum) => println!("Result: {sum}"),
+                Err(error) => println!("Result: {error:?}"),
+            }
+            match bytes::sum_of_group_priorities(input) {
+                Ok(sum) => println!("Group: {sum}"),
+                Err(error) => println!("Group: {error:?}"),
+            }
+        }
+        Some("sum") => {
+            let mut numbers = std::env::args()
+                .skip(2)
+                .map(|n| n.parse::<usize>().expect("Not a number!"));
+            let compartments = numbers.next().unwrap_or(COMPARTMENTS);
+            let group_size = numbers.next().unwrap_or(GROUP_SIZE);
+
+            match sum_of_compartment_priorities(RUCKSACKS, compartments) {
+                Ok(sum) => println!("Result: {sum}"),
+                Err(error) => println!("Result: {error:?}"),
+            }
+            match sum_of_badge_priorities(RUCKSACKS, group_size) {
+                Ok(sum) => println!("Group: {sum}"),
+                Err(error) => println!("Group: {error:?}"),
+            }
+        }
+        Some("discover") => {
+            let limit = std::env::args()
+                .nth(2)
+                .map_or(1, |n| n.parse().expect("Not a number!"));
+
+            match discovery::discover_groups(RUCKSACKS, limit) {
+                Ok(discovery) => {
+                    for (index, partition) in discovery.partitions.iter().enumerate() {
+                        let badges = partition.iter().map(|g| g.badge).collect::<String>();
+                        println!(
+                            "Partition {}: badges {badges}, priorities {}",
+                            index + 1,
+                            discovery::Discovery::badge_priorities(partition)
+                        );
+                    }
+                    match (discovery.partitions.is_empty(), discovery.exhaustive) {
+                        (true, true) => println!("No valid partition exists"),
+                        (false, true) => println!("No other partition exists"),
+                        (_, false) => println!("Stopped after {limit} partitions"),
+                    }
+                }
+                Err(error) => println!("{error:?}"),
+            }
+        }
+        Some("table") => {
+            // the puzzle's own mapping keeps to the `ItemSet` sums
+            let Some(path) = std::env::args().nth(2) else {
+                println!("Result: {}", sum_of_priorities());
+                println!("Group: {}", sum_of_group_priorities());
+                return;
+            };
+
+            match PriorityTable::load(path) {
+                Ok(table) => {
+                    match table.sum_of_priorities(RUCKSACKS, COMPARTMENTS) {
+                        Ok(sum) => println!("Result: {sum}"),
+                        Err(error) => println!("Result: {error:?}"),
+                    }
+                    match table.sum_of_group_priorities(RUCKSACKS, GROUP_SIZE) {
+                        Ok(sum) => println!("Group: {sum}"),
+                        Err(error) => println!("Group: {error:?}"),
+                    }
+                }
+                Err(error) => println!("{error}"),
+            }
+        }
+        Some("generate") => {
+            let mut numbers = std::env::args()
+                .skip(2)
+                .map(|n| n.parse::<u64>().expect("Not a number!"));
+            let seed = numbers.next().unwrap_or(0);
+            let groups = numbers.next().unwrap_or(100) as usize;
+            let generated = generator::generate(seed, groups);
+
+            println!("{}", generated.rucksacks);
+            eprintln!("Result: {}", generated.sum_of_priorities);
+            eprintln!("Group: {}", generated.sum_of_group_priorities);
+        }
+        Some("repack") => {
+            let plans = repacking::plan_all(RUCKSACKS);
+            let mut total_saved = 0;
+
+            for plan in &plans {
+                match plan {
+                    Ok(plan) => {
+                        let swaps = plan
+                            .swaps
+                            .iter()
+                            .map(|s| format!("{}<->{}", s.from_first, s.from_second))
+                            .collect::<Vec<_>>();
+                        println!(
+                            "line {}: {} swaps ({}), saves {}",
+                            plan.line,
+                            swaps.len(),
+                            swaps.join(" "),
+                            plan.priority_saved
+                        );
+                        total_saved += plan.priority_saved;
+                    }
+                    Err(RepackingError::Impossible { line }) => println!("line {line}: impossible"),
+                    Err(RepackingError::Layout(error)) => println!("{error:?}"),
+                }
+            }
+            println!("Total priority saved: {total_saved}");
+        }
+        Some("validate") => match validation::validate(RUCKSACKS) {
+            Ok(problems) if problems.is_empty() => {
+                println!("Every rucksack and group shares exactly one item")
+            }
+            Ok(problems) => {
+                for problem in problems {
+                    println!("{problem}");
+                }
+            }
+            Err(error) => println!("{error:?}"),
+        },
+        _ => {
+            println!("Result: {}", sum_of_priorities());
+            println!("Group: {}", sum_of_group_priorities());
+        }
+    }
 }
 
+// the puzzle input stays last, below the tests
+#[allow(clippy::items_after_test_module)]
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,10 +330,82 @@ mod tests {
         assert_eq!(find_common_item(&["abc", "cde", "zyxc"]), 'c');
     }
 
+    #[test]
+    fn returns_all_common_items_in_priority_order() {
+        let common = find_common_items(&["aZbcB", "cBdZea", "BZxca"]);
+
+        assert_eq!(common.iter().collect::<String>(), "acBZ");
+        assert_eq!(find_common_item(&["aZbcB", "cBdZea", "BZxca"]), 'a');
+    }
+
     #[test]
     fn returns_sum_of_group_keys() {
         assert_eq!(sum_of_group_priorities(), 2650);
     }
+
+    #[test]
+    fn supports_any_number_of_compartments_and_group_size() {
+        // p is in every third of the first rucksack, q in both halves of the others
+        let rucksacks = "apbpcp\nxqyq\nqaqb";
+
+        assert_eq!(sum_of_compartment_priorities("apbpcp", 3), Ok(16));
+        assert_eq!(sum_of_compartment_priorities(&rucksacks[7..], 2), Ok(34));
+        assert_eq!(sum_of_badge_priorities(&rucksacks[7..], 2), Ok(17));
+        // a group of one rucksack shares all of its items, so the lowest priority one wins
+        assert_eq!(sum_of_badge_priorities(rucksacks, 1), Ok(1 + 17 + 1));
+    }
+
+    #[test]
+    fn rejects_uneven_compartments_and_partial_groups() {
+        assert_eq!(
+            sum_of_compartment_priorities("abab\nabcab", 2),
+            Err(LayoutError::UnevenCompartments {
+                line: 2,
+                items: 5,
+                compartments: 2
+            })
+        );
+        assert_eq!(
+            sum_of_badge_priorities("ab\nab\nab\nab", 3),
+            Err(LayoutError::PartialGroup {
+                first_line: 4,
+                rucksacks: 1,
+                group_size: 3
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_layouts_sharing_nothing() {
+        assert_eq!(
+            sum_of_compartment_priorities(
+                "abab
+abcd",
+                2
+            ),
+            Err(LayoutError::NoCommonItem { line: 2 })
+        );
+        assert_eq!(
+            sum_of_compartment_priorities(RUCKSACKS, 3),
+            Err(LayoutError::NoCommonItem { line: 1 })
+        );
+        assert_eq!(
+            sum_of_badge_priorities(
+                "ab
+ab
+ab
+ab
+cd
+ab",
+                3
+            ),
+            Err(LayoutError::NoCommonItem { line: 4 })
+        );
+        assert_eq!(
+            sum_of_compartment_priorities("abab\na1a1", 2),
+            Err(LayoutError::UnknownItem { line: 2, item: '1' })
+        );
+    }
 }
 
 const RUCKSACKS: &str = "BccTFfTPTsffdDDqsq
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::{fmt, fs, io, path::Path};
+
+use crate::{split_compartments, split_groups, LayoutError};
+
+// the puzzle's mapping, in the same format `PriorityTable::parse` reads
+const DEFAULT_TABLE: &str = "a-z 1\nA-Z 27";
+
+#[derive(Debug)]
+pub enum TableError {
+    Io(io::Error),
+    Malformed { line: usize, content: String },
+    Duplicate { line: usize, item: char },
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableError::Io(error) => write!(f, "cannot read priority table: {error}"),
+            TableError::Malformed { line, content } => write!(
+                f,
+                "line {line}: expected \"<item> <priority>\" or \"<from>-<to> <priority>\", \
+                 found \"{content}\""
+            ),
+            TableError::Duplicate { line, item } => {
+                write!(f, "line {line}: item {item} already has a priority")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriorityTable {
+    priorities: HashMap<char, u32>,
+}
+
+impl Default for PriorityTable {
+    fn default() -> Self {
+        Self::parse(DEFAULT_TABLE).unwrap()
+    }
+}
+
+fn parse_entry(entry: &str) -> Option<(Vec<char>, u32)> {
+    let (items, priority) = entry.split_once(char::is_whitespace)?;
+    let priority = priority.trim().parse::<u32>().ok()?;
+    let chars = items.chars().collect::<Vec<_>>();
+
+    match chars[..] {
+        [item] => Some((vec![item], priority)),
+        // a range of consecutive code points with consecutive priorities
+        [from, '-', to] if from <= to => Some(((from..=to).collect(), priority)),
+        _ => None,
+    }
+}
+
+impl PriorityTable {
+    // one "<item> <priority>" or "<from>-<to> <first priority>" entry per line,
+    // blank lines and lines starting with `#` are ignored
+    pub fn parse(contents: &str) -> Result<Self, TableError> {
+        let mut priorities = HashMap::new();
+
+        for (index, line) in contents.lines().enumerate() {
+            let entry = line.trim();
+            if entry.is_empty() || entry.starts_with('#') {
+                continue;
+            }
+
+            let malformed = || TableError::Malformed {
+                line: index + 1,
+                content: entry.to_owned(),
+            };
+            let (items, first_priority) = parse_entry(entry).ok_or_else(malformed)?;
+
+            for (offset, item) in items.into_iter().enumerate() {
+                // a range running past the largest priority
+                let priority = u32::try_from(offset)
+                    .ok()
+                    .and_then(|offset| first_priority.checked_add(offset))
+                    .ok_or_else(malformed)?;
+                if priorities.insert(item, priority).is_some() {
+                    return Err(TableError::Duplicate {
+                        line: index + 1,
+                        item,
+                    });
+                }
+            }
+        }
+
+        Ok(Self { priorities })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TableError> {
+        Self::parse(&fs::read_to_string(path).map_err(TableError::Io)?)
+    }
+
+    pub fn priority(&self, item: char) -> Option<u32> {
+        self.priorities.get(&item).copied()
+    }
+
+    fn check_items(&self, line: usize, rucksack: &str) -> Result<(), LayoutError> {
+        match rucksack.chars().find(|&item| self.priority(item).is_none()) {
+            Some(item) => Err(LayoutError::UnknownItem { line, item }),
+            None => Ok(()),
+        }
+    }
+
+    // A plain scan rather than an `ItemSet`, which only holds the puzzle's letters; the
+    // puzzle sums in `main` take that faster path.
+    fn common_item_priority(&self, line: usize, buckets: &[&str]) -> Result<u32, LayoutError> {
+        buckets[0]
+            .chars()
+            .filter(|&item| buckets[1..].iter().all(|b| b.contains(item)))
+            .filter_map(|item| self.priority(item))
+            .min()
+            .ok_or(LayoutError::NoCommonItem { line })
+    }
+
+    // part one, with any number of compartments
+    pub fn sum_of_priorities(
+        &self,
+        rucksacks: &str,
+        compartments: usize,
+    ) -> Result<u32, LayoutError> {
+        rucksacks
+            .lines()
+            .enumerate()
+            .map(|(index, rucksack)| {
+                self.check_items(index + 1, rucksack)?;
+                let buckets = split_compartments(index + 1, rucksack, compartments)?;
+                self.common_item_priority(index + 1, &buckets)
+            })
+            .sum()
+    }
+
+    // part two, with any group size
+    pub fn sum_of_group_priorities(
+        &self,
+        rucksacks: &str,
+        group_size: usize,
+    ) -> Result<u32, LayoutError> {
+        let lines = rucksacks.lines().collect::<Vec<_>>();
+        for (index, rucksack) in lines.iter().enumerate() {
+            self.check_items(index + 1, rucksack)?;
+        }
+
+        split_groups(&lines, group_size)?
+            .iter()
+            .enumerate()
+            .map(|(index, group)| self.common_item_priority(index * group_size + 1, group))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{priority_for_item_type, COMPARTMENTS, GROUP_SIZE, RUCKSACKS};
+
+    #[test]
+    fn default_table_matches_the_puzzle() {
+        let table = PriorityTable::default();
+
+        for item in ('a'..='z').chain('A'..='Z') {
+            assert_eq!(
+                table.priority(item),
+                Some(priority_for_item_type(item) as u32)
+            );
+        }
+        assert_eq!(table.sum_of_priorities(RUCKSACKS, COMPARTMENTS), Ok(7428));
+        assert_eq!(
+            table.sum_of_group_priorities(RUCKSACKS, GROUP_SIZE),
+            Ok(2650)
+        );
+    }
+
+    #[test]
+    fn loads_custom_alphabets() {
+        let table = PriorityTable::parse("# digits first\n0-9 1\n\né 20\n★ 100\n").unwrap();
+
+        assert_eq!(table.priority('7'), Some(8));
+        assert_eq!(
+            table.sum_of_priorities("1é★é\n★01★", COMPARTMENTS),
+            Ok(20 + 100)
+        );
+        assert_eq!(
+            table.sum_of_group_priorities("é1\n2é\né★", GROUP_SIZE),
+            Ok(20)
+        );
+    }
+
+    #[test]
+    fn reports_unknown_items_and_bad_tables() {
+        let table = PriorityTable::default();
+
+        assert_eq!(
+            table.sum_of_priorities("abab\naéaé", COMPARTMENTS),
+            Err(LayoutError::UnknownItem {
+                line: 2, item: 'é'
+            })
+        );
+        assert_eq!(
+            table.sum_of_priorities("abab\nabcd", COMPARTMENTS),
+            Err(LayoutError::NoCommonItem { line: 2 })
+        );
+        assert!(matches!(
+            table.sum_of_group_priorities("ab\nab\nab\nab", GROUP_SIZE),
+            Err(LayoutError::PartialGroup { first_line: 4, .. })
+        ));
+        assert!(matches!(
+            PriorityTable::parse("a 1\nb"),
+            Err(TableError::Malformed { line: 2, .. })
+        ));
+        assert!(matches!(
+            PriorityTable::parse("a-c 1\nb 5"),
+            Err(TableError::Duplicate { line: 2, item: 'b' })
+        ));
+        assert!(matches!(
+            PriorityTable::parse("a-c 4294967295"),
+            Err(TableError::Malformed { line: 1, .. })
+        ));
+        assert!(matches!(
+            PriorityTable::load("does/not/exist"),
+            Err(TableError::Io(_))
+        ));
+    }
+}
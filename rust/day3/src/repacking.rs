@@ -0,0 +1,184 @@
+use crate::item_set::{item_type_for_priority, ItemSet};
+use crate::{check_items, priority_for_item_type, split_compartments};
+use crate::{LayoutError, COMPARTMENTS};
+
+// exchanges one item of the first compartment with one of the second, keeping their sizes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Swap {
+    pub from_first: char,
+    pub from_second: char,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepackingPlan {
+    pub line: usize,
+    pub swaps: Vec<Swap>,
+    // priorities of the item types no longer shared between the compartments
+    pub priority_saved: u32,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RepackingError {
+    Layout(LayoutError),
+    // no way of giving every item type a single compartment keeps the compartment sizes
+    Impossible { line: usize },
+}
+
+impl From<LayoutError> for RepackingError {
+    fn from(error: LayoutError) -> Self {
+        RepackingError::Layout(error)
+    }
+}
+
+fn count_items(compartment: &str) -> [usize; 53] {
+    let mut counts = [0; 53];
+    for item in compartment.chars() {
+        counts[priority_for_item_type(item) as usize] += 1;
+    }
+    counts
+}
+
+// Every item type ends up in one compartment: keeping it in the first means moving over
+// its items from the second, and vice versa. The first compartment must keep its size, so
+// the types kept there must add up to it exactly; among those choices, the one moving the
+// fewest items out of the first compartment needs the fewest swaps.
+pub fn plan_repacking(line: usize, rucksack: &str) -> Result<RepackingPlan, RepackingError> {
+    check_items(line, rucksack)?;
+    let compartments = split_compartments(line, rucksack, COMPARTMENTS)?;
+    let (first, second) = (compartments[0], compartments[1]);
+    let (first_counts, second_counts) = (count_items(first), count_items(second));
+    let types = (1..=52)
+        .filter(|&p| first_counts[p] + second_counts[p] > 0)
+        .collect::<Vec<_>>();
+
+    // best[i][size]: most items of the first compartment that can stay put when the
+    // first i types fill `size` slots of the first compartment
+    let capacity = first.len();
+    let mut best = vec![vec![None; capacity + 1]; types.len() + 1];
+    best[0][0] = Some(0);
+
+    for (i, &p) in types.iter().enumerate() {
+        let weight = first_counts[p] + second_counts[p];
+        for size in 0..=capacity {
+            let skip = best[i][size];
+            let keep = size
+                .checked_sub(weight)
+                .and_then(|rest| best[i][rest])
+                .map(|stay| stay + first_counts[p]);
+            best[i + 1][size] = skip.max(keep);
+        }
+    }
+
+    best[types.len()][capacity].ok_or(RepackingError::Impossible { line })?;
+
+    let (mut to_second, mut to_first) = (Vec::new(), Vec::new());
+    let mut size = capacity;
+    for (i, &p) in types.iter().enumerate().rev() {
+        let item = item_type_for_priority(p as u8);
+        let weight = first_counts[p] + second_counts[p];
+        let kept = size
+            .checked_sub(weight)
+            .and_then(|rest| best[i][rest])
+            .map(|stay| stay + first_counts[p]);
+
+        if kept.is_some() && kept >= best[i][size] {
+            to_first.extend(std::iter::repeat_n(item, second_counts[p]));
+            size -= weight;
+        } else {
+            to_second.extend(std::iter::repeat_n(item, first_counts[p]));
+        }
+    }
+
+    let shared = ItemSet::from_items(first) & ItemSet::from_items(second);
+    Ok(RepackingPlan {
+        line,
+        swaps: to_second
+            .into_iter()
+            .rev()
+            .zip(to_first.into_iter().rev())
+            .map(|(from_first, from_second)| Swap {
+                from_first,
+                from_second,
+            })
+            .collect(),
+        priority_saved: shared
+            .iter()
+            .map(|item| priority_for_item_type(item) as u32)
+            .sum(),
+    })
+}
+
+pub fn plan_all(rucksacks: &str) -> Vec<Result<RepackingPlan, RepackingError>> {
+    rucksacks
+        .lines()
+        .enumerate()
+        .map(|(index, rucksack)| plan_repacking(index + 1, rucksack))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RUCKSACKS;
+
+    fn apply(rucksack: &str, swaps: &[Swap]) -> (String, String) {
+        let (first, second) = rucksack.split_at(rucksack.len() / 2);
+        let (mut first, mut second) = (first.to_owned(), second.to_owned());
+        for swap in swaps {
+            first = first.replacen(swap.from_first, &swap.from_second.to_string(), 1);
+            second = second.replacen(swap.from_second, &swap.from_first.to_string(), 1);
+        }
+        (first, second)
+    }
+
+    #[test]
+    fn plans_the_fewest_swaps() {
+        let plan = plan_repacking(1, "abcbad").unwrap();
+
+        assert_eq!(plan.swaps.len(), 1);
+        assert_eq!(plan.priority_saved, 1 + 2);
+
+        let (first, second) = apply("abcbad", &plan.swaps);
+        assert_eq!(
+            (ItemSet::from_items(&first) & ItemSet::from_items(&second)).len(),
+            0
+        );
+    }
+
+    #[test]
+    fn proves_impossible_layouts() {
+        // six a's can't fit in a compartment of four, and b and c can't fill one alone
+        assert_eq!(
+            plan_repacking(7, "aaabaaac"),
+            Err(RepackingError::Impossible { line: 7 })
+        );
+        assert!(matches!(
+            plan_repacking(3, "abc"),
+            Err(RepackingError::Layout(LayoutError::UnevenCompartments {
+                line: 3,
+                ..
+            }))
+        ));
+        assert_eq!(
+            plan_repacking(2, "ab1b"),
+            Err(RepackingError::Layout(LayoutError::UnknownItem {
+                line: 2,
+                item: '1'
+            }))
+        );
+    }
+
+    #[test]
+    fn separates_every_solvable_puzzle_rucksack() {
+        for plan in plan_all(RUCKSACKS).into_iter().flatten() {
+            let rucksack = RUCKSACKS.lines().nth(plan.line - 1).unwrap();
+            let (first, second) = apply(rucksack, &plan.swaps);
+
+            assert_eq!(first.len(), second.len());
+            assert_eq!(
+                (ItemSet::from_items(&first) & ItemSet::from_items(&second)).len(),
+                0
+            );
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt;
+
+use crate::{check_items, find_common_items, split_compartments, split_groups};
+use crate::{LayoutError, COMPARTMENTS, GROUP_SIZE};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    // the two compartments of the rucksack on this line
+    Rucksack { line: usize },
+    // the rucksacks on these lines, both included
+    Group { first_line: usize, last_line: usize },
+}
+
+// a rucksack or group that doesn't share exactly one item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ambiguity {
+    pub scope: Scope,
+    // in priority order, empty when nothing is shared
+    pub shared: Vec<char>,
+}
+
+impl fmt::Display for Ambiguity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.scope {
+            Scope::Rucksack { line } => write!(f, "rucksack on line {line}")?,
+            Scope::Group {
+                first_line,
+                last_line,
+            } => write!(f, "group on lines {first_line}-{last_line}")?,
+        }
+
+        if self.shared.is_empty() {
+            write!(f, " shares no item")
+        } else {
+            let shared = self.shared.iter().collect::<String>();
+            write!(f, " shares {} items: {shared}", self.shared.len())
+        }
+    }
+}
+
+fn check(scope: Scope, buckets: &[&str]) -> Option<Ambiguity> {
+    let shared = find_common_items(buckets);
+
+    (shared.len() != 1).then(|| Ambiguity {
+        scope,
+        shared: shared.iter().collect(),
+    })
+}
+
+// layouts that don't split into compartments or groups are errors rather than ambiguities
+pub fn validate(rucksacks: &str) -> Result<Vec<Ambiguity>, LayoutError> {
+    let lines = rucksacks.lines().collect::<Vec<_>>();
+    let mut problems = Vec::new();
+
+    for (index, rucksack) in lines.iter().enumerate() {
+        check_items(index + 1, rucksack)?;
+        let compartments = split_compartments(index + 1, rucksack, COMPARTMENTS)?;
+        problems.extend(check(Scope::Rucksack { line: index + 1 }, &compartments));
+    }
+    for (index, group) in split_groups(&lines, GROUP_SIZE)?.iter().enumerate() {
+        let first_line = index * GROUP_SIZE + 1;
+        let scope = Scope::Group {
+            first_line,
+            last_line: first_line + group.len() - 1,
+        };
+        problems.extend(check(scope, group));
+    }
+
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RUCKSACKS;
+
+    #[test]
+    fn puzzle_input_is_unambiguous() {
+        assert_eq!(validate(RUCKSACKS), Ok(vec![]));
+    }
+
+    #[test]
+    fn flags_rucksacks_and_groups_by_line() {
+        let problems = validate("abcabc\nxyzXYZ\naqwpzP").unwrap();
+
+        assert_eq!(
+            problems,
+            [
+                Ambiguity {
+                    scope: Scope::Rucksack { line: 1 },
+                    shared: vec!['a', 'b', 'c'],
+                },
+                Ambiguity {
+                    scope: Scope::Rucksack { line: 2 },
+                    shared: vec![],
+                },
+                Ambiguity {
+                    scope: Scope::Rucksack { line: 3 },
+                    shared: vec![],
+                },
+                Ambiguity {
+                    scope: Scope::Group {
+                        first_line: 1,
+                        last_line: 3
+                    },
+                    shared: vec![],
+                },
+            ]
+        );
+        assert_eq!(
+            problems[0].to_string(),
+            "rucksack on line 1 shares 3 items: abc"
+        );
+        assert_eq!(problems[3].to_string(), "group on lines 1-3 shares no item");
+    }
+
+    #[test]
+    fn rejects_partial_groups_and_uneven_rucksacks() {
+        assert_eq!(
+            validate("abab\nabab\nabab\nabab"),
+            Err(LayoutError::PartialGroup {
+                first_line: 4,
+                rucksacks: 1,
+                group_size: GROUP_SIZE
+            })
+        );
+        assert!(matches!(
+            validate("abab\naba"),
+            Err(LayoutError::UnevenCompartments { line: 2, .. })
+        ));
+        assert_eq!(
+            validate("a1a1\nbbbb\ncccc"),
+            Err(LayoutError::UnknownItem { line: 1, item: '1' })
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use crate::group::AssignmentError;
+use crate::interval::{Interval, Section};
+use crate::parse_pairs;
+
+// redundancy glyphs of the ruler, from a couple of elves up to the busiest section
+const DENSITY: &[u8] = b".:-=+*#%@";
+// glyphs the ruler may use before it scales up
+const RULER_WIDTH: i128 = 100;
+
+// how many elves are assigned each section, from the first assigned one to the last, as
+// maximal runs of sections at the same depth so sparse camps stay small
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coverage<T> {
+    pub runs: Vec<(Interval<T>, usize)>,
+}
+
+impl<T: Section> Coverage<T> {
+    pub fn new(ranges: impl IntoIterator<Item = Interval<T>>) -> Self {
+        // like `SectionIndex::busiest_section`, a range adds an elf at its start and removes
+        // it once past its end
+        let mut events = ranges
+            .into_iter()
+            .flat_map(|r| [(r.start, false, 1), (r.end, true, -1)])
+            .collect::<Vec<_>>();
+        events.sort();
+
+        let mut runs: Vec<(Interval<T>, usize)> = Vec::new();
+        let mut depth = 0isize;
+        for (index, &(section, past_end, change)) in events.iter().enumerate() {
+            depth += change;
+            let Some(&(next, next_past_end, _)) = events.get(index + 1) else {
+                break;
+            };
+
+            // the sections up to the next event, none when it's right after this one
+            let start = if past_end {
+                section.successor()
+            } else {
+                Some(section)
+            };
+            let end = if next_past_end {
+                Some(next)
+            } else {
+                next.predecessor()
+            };
+            let (Some(start), Some(end)) = (start, end) else {
+                continue;
+            };
+            if start > end {
+                continue;
+            }
+
+            match runs.last_mut() {
+                Some((run, run_depth)) if *run_depth == depth as usize => run.end = end,
+                _ => runs.push((Interval::new(start, end), depth as usize)),
+            }
+        }
+
+        Self { runs }
+    }
+
+    pub fn parse(pairs: &str) -> Result<Self, AssignmentError> {
+        Ok(Self::new(
+            parse_pairs(pairs)?.into_iter().flat_map(|(a, b)| [a, b]),
+        ))
+    }
+
+    // maximal runs of consecutive sections whose depth passes `filter`
+    fn runs(&self, filter: impl Fn(usize) -> bool) -> Vec<Interval<T>> {
+        let mut runs: Vec<Interval<T>> = Vec::new();
+
+        for &(run, depth) in &self.runs {
+            if !filter(depth) {
+                continue;
+            }
+            match runs.last_mut() {
+                Some(last) if last.end.successor() == Some(run.start) => last.end = run.end,
+                _ => runs.push(run),
+            }
+        }
+
+        runs
+    }
+
+    pub fn union(&self) -> Vec<Interval<T>> {
+        self.runs(|depth| depth > 0)
+    }
+
+    pub fn gaps(&self) -> Vec<Interval<T>> {
+        self.runs(|depth| depth == 0)
+    }
+
+    // sections only one elf is assigned, nobody covers for them
+    pub fn single_points_of_failure(&self) -> Vec<Interval<T>> {
+        self.runs(|depth| depth == 1)
+    }
+
+    pub fn max_redundancy(&self) -> usize {
+        self.runs.iter().map(|&(_, depth)| depth).max().unwrap_or(0)
+    }
+
+    pub fn most_redundant(&self) -> Vec<Interval<T>> {
+        let max = self.max_redundancy();
+        self.runs(|depth| max > 0 && depth == max)
+    }
+
+    // A tick every ten glyphs above one glyph per section, or per ten, hundred... sections
+    // on camps wider than the ruler. Each glyph shows the busiest section it stands for:
+    // blank when uncovered, `1` for a single elf, then denser as it nears the busiest one.
+    pub fn ruler(&self) -> String {
+        let (Some((first, _)), Some((last, _))) = (self.runs.first(), self.runs.last()) else {
+            return String::from("\n\n");
+        };
+        let (first, last) = (first.start.to_i128(), last.end.to_i128());
+
+        let mut scale = 1;
+        while last.div_euclid(scale) - first.div_euclid(scale) >= RULER_WIDTH {
+            scale *= 10;
+        }
+        let origin = first.div_euclid(scale);
+        let block = |section: i128| (section.div_euclid(scale) - origin) as usize;
+
+        let mut depths = vec![0; block(last) + 1];
+        for &(run, depth) in &self.runs {
+            for busiest in &mut depths[block(run.start.to_i128())..=block(run.end.to_i128())] {
+                *busiest = depth.max(*busiest);
+            }
+        }
+
+        let max = self.max_redundancy();
+        let mut ticks = String::new();
+        let mut glyphs = String::new();
+
+        for (offset, depth) in depths.into_iter().enumerate() {
+            let section = (origin + offset as i128) * scale;
+            // leaving out ticks that would run into the previous label
+            if ticks.len() == offset {
+                if section % (10 * scale) == 0 || offset == 0 {
+                    ticks.push_str(&format!("|{}", if offset == 0 { first } else { section }));
+                } else {
+                    ticks.push(' ');
+                }
+            }
+
+            glyphs.push(match depth {
+                0 => ' ',
+                1 => '1',
+                _ => {
+                    let level = (depth - 2) * (DENSITY.len() - 1) / (max - 2).max(1);
+                    DENSITY[level] as char
+                }
+            });
+        }
+
+        format!("{}\n{glyphs}\n", ticks.trim_end())
+    }
+
+    pub fn report(&self) -> String {
+        let list = |runs: Vec<Interval<T>>| {
+            if runs.is_empty() {
+                return String::from("none");
+            }
+            runs.iter()
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        format!(
+            "covered: {}\ngaps: {}\nsingle points of failure: {}\n\
+             most redundant ({} elves): {}\n\n{}",
+            list(self.union()),
+            list(self.gaps()),
+            list(self.single_points_of_failure()),
+            self.max_redundancy(),
+            list(self.most_redundant()),
+            self.ruler()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ASSIGNMENT_PAIRS;
+
+    #[test]
+    fn reports_union_gaps_and_redundancy() {
+        let coverage = Coverage::<i32>::parse("2-4,3-5\n8-9,9-9\n11-11,3-3").unwrap();
+
+        let run = |start, end, depth| (Interval::new(start, end), depth);
+        assert_eq!(
+            coverage.runs,
+            [
+                run(2, 2, 1),
+                run(3, 3, 3),
+                run(4, 4, 2),
+                run(5, 5, 1),
+                run(6, 7, 0),
+                run(8, 8, 1),
+                run(9, 9, 2),
+                run(10, 10, 0),
+                run(11, 11, 1)
+            ]
+        );
+        assert_eq!(
+            coverage.union(),
+            [
+                Interval::new(2, 5),
+                Interval::new(8, 9),
+                Interval::new(11, 11)
+            ]
+        );
+        assert_eq!(
+            coverage.gaps(),
+            [Interval::new(6, 7), Interval::new(10, 10)]
+        );
+        assert_eq!(
+            coverage.single_points_of_failure(),
+            [
+                Interval::new(2, 2),
+                Interval::new(5, 5),
+                Interval::new(8, 8),
+                Interval::new(11, 11)
+            ]
+        );
+        assert_eq!(coverage.most_redundant(), [Interval::new(3, 3)]);
+        assert_eq!(coverage.ruler(), "|2      |10\n1@.1  1. 1\n");
+    }
+
+    #[test]
+    fn covers_the_puzzle_camp() {
+        let coverage = Coverage::<i32>::parse(ASSIGNMENT_PAIRS).unwrap();
+
+        assert_eq!(coverage.union(), [Interval::new(1, 99)]);
+        assert_eq!(coverage.gaps(), []);
+        let depths = coverage
+            .runs
+            .iter()
+            .map(|(run, depth)| run.sections() as usize * depth);
+        assert_eq!(depths.sum::<usize>(), {
+            parse_pairs::<i32>(ASSIGNMENT_PAIRS)
+                .unwrap()
+                .into_iter()
+                .map(|(a, b)| (a.end - a.start + b.end - b.start + 2) as usize)
+                .sum::<usize>()
+        });
+        assert_eq!(coverage.max_redundancy(), 1030);
+    }
+
+    #[test]
+    fn handles_no_assignments() {
+        let coverage = Coverage::<i32>::parse("").unwrap();
+
+        assert_eq!(coverage.union(), []);
+        assert_eq!(coverage.max_redundancy(), 0);
+        assert_eq!(coverage.ruler(), "\n\n");
+    }
+
+    #[test]
+    fn scales_the_ruler_of_sparse_camps() {
+        let coverage = Coverage::<i64>::parse("1-1,9000000000-9000000000").unwrap();
+        let report = coverage.report();
+
+        assert!(report.starts_with("covered: 1-1, 9000000000-9000000000\n"));
+        // a glyph per hundred million sections
+        let glyphs = report.lines().last().unwrap();
+        assert_eq!(glyphs, format!("1{}1", " ".repeat(89)));
+        assert!(report.contains("\n|1        |1000000000         |3000000000"));
+    }
+
+    #[test]
+    fn handles_sparse_and_extreme_ranges() {
+        let coverage =
+            Coverage::<i32>::parse("-2147483648-2147483647,5-5\n1-1,2147483647-2147483647")
+                .unwrap();
+
+        assert_eq!(coverage.runs.len(), 6);
+        assert_eq!(coverage.union(), [Interval::new(i32::MIN, i32::MAX)]);
+        assert_eq!(coverage.gaps(), []);
+        assert_eq!(
+            coverage.most_redundant(),
+            [
+                Interval::new(1, 1),
+                Interval::new(5, 5),
+                Interval::new(i32::MAX, i32::MAX)
+            ]
+        );
+        assert_eq!(
+            Coverage::<i32>::parse("1-1,2000000000-2000000000")
+                .unwrap()
+                .gaps(),
+            [Interval::new(2, 1_999_999_999)]
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use std::{error::Error, fmt};
+
+use crate::interval::{Interval, IntervalError, Section};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssignmentError {
+    // 1-based line, and the range as written
+    Malformed { line: usize, range: String },
+    Reversed { line: usize, range: String },
+    Empty { line: usize, range: String },
+    // a pair line holding some other number of ranges
+    NotAPair { line: usize, ranges: usize },
+}
+
+impl fmt::Display for AssignmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssignmentError::Malformed { line, range } => write!(
+                f,
+                "line {line}: expected \"<start>-<end>\" or \"[<start>,<end>)\", found \"{range}\""
+            ),
+            AssignmentError::Reversed { line, range } => {
+                write!(f, "line {line}: range {range} ends before it starts")
+            }
+            AssignmentError::Empty { line, range } => {
+                write!(f, "line {line}: range {range} holds no section")
+            }
+            AssignmentError::NotAPair { line, ranges } => {
+                write!(f, "line {line}: expected a pair of ranges, found {ranges}")
+            }
+        }
+    }
+}
+
+impl Error for AssignmentError {}
+
+// the ranges of every elf on one line, in the order given
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group<T> {
+    pub line: usize,
+    pub ranges: Vec<Interval<T>>,
+}
+
+// comma separated ranges, as many as there are elves on the line; commas inside
+// `[a,b]` brackets belong to the range
+pub fn parse_ranges<T: Section>(
+    line: usize,
+    ranges: &str,
+) -> Result<Vec<Interval<T>>, AssignmentError> {
+    let mut depth = 0;
+    let separators = ranges.char_indices().filter(|&(_, c)| {
+        match c {
+            '[' => depth += 1,
+            ']' | ')' => depth -= 1,
+            _ => {}
+        }
+        c == ',' && depth == 0
+    });
+    let bounds = std::iter::once(0)
+        .chain(separators.map(|(index, _)| index + 1))
+        .collect::<Vec<_>>();
+    let ends = bounds[1..].iter().map(|&b| b - 1).chain([ranges.len()]);
+
+    bounds
+        .iter()
+        .zip(ends)
+        .map(|(&from, to)| {
+            let range = ranges[from..to].trim();
+            range.parse().map_err(|error| {
+                let range = range.to_owned();
+                match error {
+                    IntervalError::Malformed => AssignmentError::Malformed { line, range },
+                    IntervalError::Reversed => AssignmentError::Reversed { line, range },
+                    IntervalError::Empty => AssignmentError::Empty { line, range },
+                }
+            })
+        })
+        .collect()
+}
+
+pub fn parse_groups<T: Section>(input: &str) -> Result<Vec<Group<T>>, AssignmentError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            Ok(Group {
+                line: index + 1,
+                ranges: parse_ranges(index + 1, line)?,
+            })
+        })
+        .collect()
+}
+
+impl<T: Section> Group<T> {
+    // sorted by start, a range overlaps an earlier one iff it starts before they all end
+    pub fn any_pair_overlaps(&self) -> bool {
+        let mut ranges = self.ranges.clone();
+        ranges.sort_by_key(|r| r.start);
+
+        ranges
+            .iter()
+            .scan(None, |furthest: &mut Option<T>, range| {
+                let overlaps = furthest.is_some_and(|end| range.start <= end);
+                *furthest = Some(furthest.map_or(range.end, |end| end.max(range.end)));
+                Some(overlaps)
+            })
+            .any(|overlaps| overlaps)
+    }
+
+    // the sections every elf of the group is assigned
+    pub fn common_sections(&self) -> Option<Interval<T>> {
+        let (first, rest) = self.ranges.split_first()?;
+        rest.iter()
+            .try_fold(*first, |common, range| common.intersection(range))
+    }
+
+    // index of the first range containing all the others
+    pub fn containing_all(&self) -> Option<usize> {
+        let start = self.ranges.iter().map(|r| r.start).min()?;
+        let end = self.ranges.iter().map(|r| r.end).max()?;
+
+        self.ranges
+            .iter()
+            .position(|r| r.start == start && r.end == end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ASSIGNMENT_PAIRS;
+
+    #[test]
+    fn answers_group_queries() {
+        let groups = parse_groups("2-4,6-8,10-12\n2-8,3-7,6-6\n1-3,3-5,2-9\n5-5").unwrap();
+        let answers = groups
+            .iter()
+            .map(|g| {
+                (
+                    g.any_pair_overlaps(),
+                    g.common_sections(),
+                    g.containing_all(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            answers,
+            [
+                (false, None, None),
+                (true, Some(Interval::new(6, 6)), Some(0)),
+                (true, Some(Interval::new(3, 3)), None),
+                (false, Some(Interval::new(5, 5)), Some(0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn pairs_are_groups_of_two() {
+        let groups = parse_groups::<i32>(ASSIGNMENT_PAIRS).unwrap();
+
+        assert!(groups.iter().all(|g| g.ranges.len() == 2));
+        assert_eq!(
+            groups
+                .iter()
+                .filter(|g| g.containing_all().is_some())
+                .count(),
+            466
+        );
+        assert_eq!(groups.iter().filter(|g| g.any_pair_overlaps()).count(), 865);
+    }
+
+    #[test]
+    fn parses_bracketed_and_wide_ranges() {
+        let groups = parse_groups::<i64>("[1,5),[5,9], 4-4\n[-3,0),9000000000-9000000001").unwrap();
+
+        assert_eq!(
+            groups[0].ranges,
+            [
+                Interval::new(1, 4),
+                Interval::new(5, 9),
+                Interval::new(4, 4)
+            ]
+        );
+        assert_eq!(groups[0].containing_all(), None);
+        assert_eq!(
+            groups[1].ranges,
+            [
+                Interval::new(-3, -1),
+                Interval::new(9_000_000_000, 9_000_000_001)
+            ]
+        );
+        assert_eq!(
+            parse_groups::<u64>("[2,2)"),
+            Err(AssignmentError::Empty {
+                line: 1,
+                range: String::from("[2,2)")
+            })
+        );
+    }
+
+    #[test]
+    fn reports_malformed_and_reversed_ranges() {
+        assert_eq!(
+            parse_groups::<i32>("2-4,6-8\n1-2,9-3,4-5"),
+            Err(AssignmentError::Reversed {
+                line: 2,
+                range: String::from("9-3")
+            })
+        );
+        assert_eq!(
+            parse_groups::<i32>("2-4,6-8,x-1"),
+            Err(AssignmentError::Malformed {
+                line: 1,
+                range: String::from("x-1")
+            })
+        );
+        assert_eq!(
+            parse_groups::<i32>("2-4,,6-8").unwrap_err().to_string(),
+            "line 1: expected \"<start>-<end>\" or \"[<start>,<end>)\", found \"\""
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::group::AssignmentError;
+use crate::interval::{Interval, Section};
+use crate::parse_pairs;
+
+// 1-based line of the pair, and which of its elves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Elf {
+    pub line: usize,
+    pub position: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Assignment<T> {
+    pub elf: Elf,
+    pub range: Interval<T>,
+}
+
+// the section assigned to the most elves, the first one when several tie
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Busiest<T> {
+    pub section: T,
+    pub elves: usize,
+}
+
+pub fn parse_assignments<T: Section>(pairs: &str) -> Result<Vec<Assignment<T>>, AssignmentError> {
+    Ok(parse_pairs(pairs)?
+        .into_iter()
+        .enumerate()
+        .flat_map(|(index, (a, b))| {
+            [(1, a), (2, b)].map(|(position, range)| Assignment {
+                elf: Elf {
+                    line: index + 1,
+                    position,
+                },
+                range,
+            })
+        })
+        .collect())
+}
+
+// A static interval tree: the assignments sorted by start form an implicit balanced
+// search tree, the middle of every slice being its root, and each node remembers the
+// furthest end of its subtree so whole subtrees left of a query can be skipped.
+pub struct SectionIndex<T> {
+    assignments: Vec<Assignment<T>>,
+    max_ends: Vec<T>,
+}
+
+impl<T: Section> SectionIndex<T> {
+    pub fn new(mut assignments: Vec<Assignment<T>>) -> Self {
+        assignments.sort_by_key(|a| (a.range.start, a.range.end, a.elf));
+        let mut max_ends = assignments.iter().map(|a| a.range.end).collect::<Vec<_>>();
+        fill_max_ends(&assignments, &mut max_ends);
+
+        Self {
+            assignments,
+            max_ends,
+        }
+    }
+
+    pub fn parse(pairs: &str) -> Result<Self, AssignmentError> {
+        Ok(Self::new(parse_assignments(pairs)?))
+    }
+
+    // every elf assigned at least one section of `range`, in line order
+    pub fn overlapping(&self, range: Interval<T>) -> Vec<Elf> {
+        let mut elves = Vec::new();
+        self.collect_overlapping(0, self.assignments.len(), range, &mut elves);
+        elves.sort();
+        elves
+    }
+
+    fn collect_overlapping(
+        &self,
+        from: usize,
+        to: usize,
+        range: Interval<T>,
+        elves: &mut Vec<Elf>,
+    ) {
+        if from >= to {
+            return;
+        }
+        let mid = from + (to - from) / 2;
+        if self.max_ends[mid] < range.start {
+            return;
+        }
+
+        self.collect_overlapping(from, mid, range, elves);
+
+        let node = &self.assignments[mid];
+        if node.range.start <= range.end {
+            if node.range.end >= range.start {
+                elves.push(node.elf);
+            }
+            self.collect_overlapping(mid + 1, to, range, elves);
+        }
+    }
+
+    // Sweeps the assignments by start, keeping the ones still running in a heap by end;
+    // each new assignment overlaps exactly the ones left after dropping those ended.
+    // Pairs on the same line are left out, that's what `verify_ranges` is for.
+    pub fn overlapping_pairs(&self) -> Vec<(Elf, Elf)> {
+        let mut active: BinaryHeap<Reverse<(T, usize)>> = BinaryHeap::new();
+        let mut pairs = Vec::new();
+
+        for (index, assignment) in self.assignments.iter().enumerate() {
+            while active
+                .peek()
+                .is_some_and(|Reverse((end, _))| *end < assignment.range.start)
+            {
+                active.pop();
+            }
+
+            for Reverse((_, other)) in &active {
+                let other = self.assignments[*other].elf;
+                if other.line != assignment.elf.line {
+                    pairs.push((other.min(assignment.elf), other.max(assignment.elf)));
+                }
+            }
+            active.push(Reverse((assignment.range.end, index)));
+        }
+
+        pairs.sort();
+        pairs
+    }
+
+    pub fn busiest_section(&self) -> Option<Busiest<T>> {
+        // a range adds an elf at its start and removes it once past its end, flagged rather
+        // than moved to the next section so ranges ending at the last one don't overflow
+        let mut events = self
+            .assignments
+            .iter()
+            .flat_map(|a| [(a.range.start, false, 1), (a.range.end, true, -1)])
+            .collect::<Vec<_>>();
+        // removals after the additions of their section, but before the next one's
+        events.sort();
+
+        let mut elves = 0;
+        let mut busiest: Option<Busiest<T>> = None;
+        for (section, _, change) in events {
+            elves += change;
+            if busiest.is_none_or(|b| elves as usize > b.elves) {
+                busiest = Some(Busiest {
+                    section,
+                    elves: elves as usize,
+                });
+            }
+        }
+
+        busiest
+    }
+}
+
+fn fill_max_ends<T: Section>(assignments: &[Assignment<T>], max_ends: &mut [T]) -> Option<T> {
+    if assignments.is_empty() {
+        return None;
+    }
+    let mid = assignments.len() / 2;
+    let left = fill_max_ends(&assignments[..mid], &mut max_ends[..mid]);
+    let right = fill_max_ends(&assignments[mid + 1..], &mut max_ends[mid + 1..]);
+
+    max_ends[mid] = [left, right]
+        .into_iter()
+        .flatten()
+        .fold(assignments[mid].range.end, T::max);
+    Some(max_ends[mid])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ASSIGNMENT_PAIRS;
+
+    fn brute_force_overlapping(pairs: &str, range: Interval<i32>) -> Vec<Elf> {
+        let index = SectionIndex::<i32>::parse(pairs).unwrap();
+        let mut elves = index
+            .assignments
+            .iter()
+            .filter(|a| a.range.start <= range.end && a.range.end >= range.start)
+            .map(|a| a.elf)
+            .collect::<Vec<_>>();
+        elves.sort();
+        elves
+    }
+
+    #[test]
+    fn finds_elves_overlapping_a_range() {
+        let index = SectionIndex::parse("2-4,6-8\n2-3,4-5\n5-7,7-9").unwrap();
+        let elf = |line, position| Elf { line, position };
+
+        assert_eq!(
+            index.overlapping(Interval::new(4, 5)),
+            [elf(1, 1), elf(2, 2), elf(3, 1)]
+        );
+        assert_eq!(index.overlapping(Interval::new(10, 12)), []);
+
+        for (start, end) in [(1, 1), (13, 40), (50, 50), (90, 99)] {
+            let range = Interval::new(start, end);
+            assert_eq!(
+                SectionIndex::parse(ASSIGNMENT_PAIRS)
+                    .unwrap()
+                    .overlapping(range),
+                brute_force_overlapping(ASSIGNMENT_PAIRS, range)
+            );
+        }
+    }
+
+    #[test]
+    fn lists_pairs_across_lines_and_the_busiest_section() {
+        let index = SectionIndex::parse("2-4,6-8\n2-3,4-5\n5-7,7-9").unwrap();
+        let elf = |line, position| Elf { line, position };
+
+        assert_eq!(
+            index.overlapping_pairs(),
+            [
+                (elf(1, 1), elf(2, 1)),
+                (elf(1, 1), elf(2, 2)),
+                (elf(1, 2), elf(3, 1)),
+                (elf(1, 2), elf(3, 2)),
+                (elf(2, 2), elf(3, 1)),
+            ]
+        );
+        assert_eq!(
+            index.busiest_section(),
+            Some(Busiest {
+                section: 7,
+                elves: 3
+            })
+        );
+        assert_eq!(
+            SectionIndex::<i32>::parse("1-2147483647,3-4")
+                .unwrap()
+                .busiest_section(),
+            Some(Busiest {
+                section: 3,
+                elves: 2
+            })
+        );
+        assert_eq!(
+            SectionIndex::<i64>::parse("1-9223372036854775807,9000000000-9000000001")
+                .unwrap()
+                .busiest_section(),
+            Some(Busiest {
+                section: 9_000_000_000,
+                elves: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,360 @@
+use std::cmp::Ordering::{Equal, Greater, Less};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::group::AssignmentError;
+use crate::parse_pairs;
+
+// both bounds included, like the section assignments; half-open ranges are stored with
+// their last section as the end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+// how the first interval relates to the second, the inverse relations come right after
+// the relation they invert
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AllenRelation {
+    Before,
+    After,
+    Meets,
+    MetBy,
+    Overlaps,
+    OverlappedBy,
+    Starts,
+    StartedBy,
+    During,
+    Contains,
+    Finishes,
+    FinishedBy,
+    Equals,
+}
+
+impl AllenRelation {
+    pub const ALL: [AllenRelation; 13] = [
+        AllenRelation::Before,
+        AllenRelation::After,
+        AllenRelation::Meets,
+        AllenRelation::MetBy,
+        AllenRelation::Overlaps,
+        AllenRelation::OverlappedBy,
+        AllenRelation::Starts,
+        AllenRelation::StartedBy,
+        AllenRelation::During,
+        AllenRelation::Contains,
+        AllenRelation::Finishes,
+        AllenRelation::FinishedBy,
+        AllenRelation::Equals,
+    ];
+
+    pub fn inverse(self) -> Self {
+        match self {
+            AllenRelation::Before => AllenRelation::After,
+            AllenRelation::After => AllenRelation::Before,
+            AllenRelation::Meets => AllenRelation::MetBy,
+            AllenRelation::MetBy => AllenRelation::Meets,
+            AllenRelation::Overlaps => AllenRelation::OverlappedBy,
+            AllenRelation::OverlappedBy => AllenRelation::Overlaps,
+            AllenRelation::Starts => AllenRelation::StartedBy,
+            AllenRelation::StartedBy => AllenRelation::Starts,
+            AllenRelation::During => AllenRelation::Contains,
+            AllenRelation::Contains => AllenRelation::During,
+            AllenRelation::Finishes => AllenRelation::FinishedBy,
+            AllenRelation::FinishedBy => AllenRelation::Finishes,
+            AllenRelation::Equals => AllenRelation::Equals,
+        }
+    }
+}
+
+impl fmt::Display for AllenRelation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AllenRelation::Before => "before",
+            AllenRelation::After => "after",
+            AllenRelation::Meets => "meets",
+            AllenRelation::MetBy => "met by",
+            AllenRelation::Overlaps => "overlaps",
+            AllenRelation::OverlappedBy => "overlapped by",
+            AllenRelation::Starts => "starts",
+            AllenRelation::StartedBy => "started by",
+            AllenRelation::During => "during",
+            AllenRelation::Contains => "contains",
+            AllenRelation::Finishes => "finishes",
+            AllenRelation::FinishedBy => "finished by",
+            AllenRelation::Equals => "equals",
+        };
+        f.pad(name)
+    }
+}
+
+// integer types usable as section ids
+pub trait Section: Copy + Ord + FromStr + fmt::Display {
+    // `None` at the type's minimum
+    fn predecessor(self) -> Option<Self>;
+    // `None` at the type's maximum
+    fn successor(self) -> Option<Self>;
+    // the first section of the puzzle's camp
+    const ONE: Self;
+    // wide enough for every integer section
+    fn to_i128(self) -> i128;
+    // how many sections from `start` to `end`, both included
+    fn count(start: Self, end: Self) -> u128 {
+        (end.to_i128() - start.to_i128()) as u128 + 1
+    }
+}
+
+macro_rules! impl_section {
+    ($($t:ty),*) => {
+        $(impl Section for $t {
+            fn predecessor(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn successor(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            const ONE: Self = 1;
+
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+        })*
+    };
+}
+
+impl_section!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inclusivity {
+    // `a-b` or `[a,b]`
+    Closed,
+    // `[a,b)`, the end isn't part of the range
+    HalfOpen,
+}
+
+impl<T: Ord + Copy> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        assert!(start <= end, "Interval ends before it starts!");
+        Self { start, end }
+    }
+
+    // the sections both intervals cover
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        (start <= end).then_some(Self { start, end })
+    }
+}
+
+impl<T: Section> Interval<T> {
+    pub fn from_bounds(start: T, end: T, inclusivity: Inclusivity) -> Result<Self, IntervalError> {
+        let end = match inclusivity {
+            Inclusivity::Closed => end,
+            Inclusivity::HalfOpen if end == start => return Err(IntervalError::Empty),
+            Inclusivity::HalfOpen => end.predecessor().ok_or(IntervalError::Reversed)?,
+        };
+
+        if start <= end {
+            Ok(Self::new(start, end))
+        } else {
+            Err(IntervalError::Reversed)
+        }
+    }
+
+    // Compares the bounds as Allen does, with sections as the points: intervals meet when
+    // the second starts on the section right after the first ends, and sharing a boundary
+    // section overlaps. Single-section intervals resolve to the first relation they satisfy.
+    pub fn relation(&self, other: &Self) -> AllenRelation {
+        match (self.start.cmp(&other.start), self.end.cmp(&other.end)) {
+            (Equal, Equal) => AllenRelation::Equals,
+            (Equal, Less) => AllenRelation::Starts,
+            (Equal, Greater) => AllenRelation::StartedBy,
+            (Greater, Equal) => AllenRelation::Finishes,
+            (Less, Equal) => AllenRelation::FinishedBy,
+            (Greater, Less) => AllenRelation::During,
+            (Less, Greater) => AllenRelation::Contains,
+            (Less, Less) if self.end >= other.start => AllenRelation::Overlaps,
+            (Less, Less) if self.end.successor() == Some(other.start) => AllenRelation::Meets,
+            (Less, Less) => AllenRelation::Before,
+            (Greater, Greater) => other.relation(self).inverse(),
+        }
+    }
+
+    pub fn sections(&self) -> u128 {
+        T::count(self.start, self.end)
+    }
+
+    // every section, in order
+    pub fn iter(&self) -> impl Iterator<Item = T> {
+        let end = self.end;
+        std::iter::successors(Some(self.start), move |&section| {
+            (section < end).then(|| section.successor()).flatten()
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntervalError {
+    // neither "<start>-<end>" nor "[<start>,<end>]" or "[<start>,<end>)" with two numbers
+    Malformed,
+    // ends before it starts, like `9-3`
+    Reversed,
+    // a half-open range without any section, like `[4,4)`
+    Empty,
+}
+
+impl<T: Section> FromStr for Interval<T> {
+    type Err = IntervalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let parse = |n: &str| n.trim().parse().map_err(|_| IntervalError::Malformed);
+
+        if let Some(bounds) = s.strip_prefix('[') {
+            let (bounds, inclusivity) = if let Some(bounds) = bounds.strip_suffix(']') {
+                (bounds, Inclusivity::Closed)
+            } else if let Some(bounds) = bounds.strip_suffix(')') {
+                (bounds, Inclusivity::HalfOpen)
+            } else {
+                return Err(IntervalError::Malformed);
+            };
+            let (start, end) = bounds.split_once(',').ok_or(IntervalError::Malformed)?;
+            return Self::from_bounds(parse(start)?, parse(end)?, inclusivity);
+        }
+
+        // the separator is the first dash that isn't the sign of the start
+        let dash = s
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| c == '-')
+            .ok_or(IntervalError::Malformed)?
+            .0;
+        Self::from_bounds(
+            parse(&s[..dash])?,
+            parse(&s[dash + 1..])?,
+            Inclusivity::Closed,
+        )
+    }
+}
+
+// how many pairs of every relation, in `AllenRelation::ALL` order
+pub fn relation_counts(pairs: &str) -> Result<Vec<(AllenRelation, usize)>, AssignmentError> {
+    let mut counts = AllenRelation::ALL.map(|relation| (relation, 0));
+
+    for (a, b) in parse_pairs::<i64>(pairs)? {
+        let relation = a.relation(&b);
+        counts.iter_mut().find(|(r, _)| *r == relation).unwrap().1 += 1;
+    }
+
+    Ok(counts.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ASSIGNMENT_PAIRS;
+
+    #[test]
+    fn classifies_all_thirteen_relations() {
+        let cases = [
+            ((1, 2), (4, 6), AllenRelation::Before),
+            ((1, 3), (4, 6), AllenRelation::Meets),
+            ((1, 4), (4, 6), AllenRelation::Overlaps),
+            ((1, 5), (4, 6), AllenRelation::Overlaps),
+            ((4, 5), (4, 6), AllenRelation::Starts),
+            ((5, 5), (4, 6), AllenRelation::During),
+            ((5, 6), (4, 6), AllenRelation::Finishes),
+            ((4, 6), (4, 6), AllenRelation::Equals),
+        ];
+
+        for ((a, b), (c, d), relation) in cases {
+            let (first, second) = (Interval::new(a, b), Interval::new(c, d));
+            assert_eq!(first.relation(&second), relation);
+            assert_eq!(second.relation(&first), relation.inverse());
+        }
+    }
+
+    #[test]
+    fn counts_relations_of_the_puzzle() {
+        let counts = relation_counts(ASSIGNMENT_PAIRS).unwrap();
+        let count = |relations: &[AllenRelation]| {
+            counts
+                .iter()
+                .filter(|(r, _)| relations.contains(r))
+                .map(|(_, n)| n)
+                .sum::<usize>()
+        };
+
+        assert_eq!(count(&AllenRelation::ALL), 1000);
+        // the pairs sharing no section, whether or not they are adjacent
+        assert_eq!(
+            count(&[
+                AllenRelation::Before,
+                AllenRelation::After,
+                AllenRelation::Meets,
+                AllenRelation::MetBy,
+            ]),
+            1000 - 865
+        );
+        assert_eq!(
+            count(&[
+                AllenRelation::Starts,
+                AllenRelation::StartedBy,
+                AllenRelation::During,
+                AllenRelation::Contains,
+                AllenRelation::Finishes,
+                AllenRelation::FinishedBy,
+                AllenRelation::Equals,
+            ]),
+            466
+        );
+    }
+
+    #[test]
+    fn parses_other_integers_and_half_open_ranges() {
+        assert_eq!("[3,7)".parse(), Ok(Interval::new(3, 6)));
+        assert_eq!("[3, 7]".parse(), Ok(Interval::new(3, 7)));
+        assert_eq!("-5--3".parse(), Ok(Interval::new(-5i64, -3)));
+        assert_eq!(
+            "[9000000000,9000000002)"
+                .parse::<Interval<u64>>()
+                .map(|r| r.sections()),
+            Ok(2)
+        );
+        assert_eq!(
+            "0-18446744073709551615"
+                .parse::<Interval<u64>>()
+                .map(|r| r.sections()),
+            Ok(1 << 64)
+        );
+        assert_eq!("[4,4)".parse::<Interval<u8>>(), Err(IntervalError::Empty));
+        assert_eq!(
+            "[4,3)".parse::<Interval<u8>>(),
+            Err(IntervalError::Reversed)
+        );
+        assert_eq!(
+            "[3,7".parse::<Interval<i32>>(),
+            Err(IntervalError::Malformed)
+        );
+        assert_eq!(
+            "300-301".parse::<Interval<u8>>(),
+            Err(IntervalError::Malformed)
+        );
+    }
+
+    #[test]
+    fn rejects_reversed_ranges() {
+        assert_eq!("2-8".parse(), Ok(Interval::new(2, 8)));
+        assert_eq!("8-2".parse::<Interval<i32>>(), Err(IntervalError::Reversed));
+        assert_eq!("8-".parse::<Interval<i32>>(), Err(IntervalError::Malformed));
+    }
+}
@@ -1,53 +1,266 @@
+mod coverage;
+mod group;
+mod index;
+mod interval;
+mod metrics;
+mod planner;
+mod render;
+
+use group::AssignmentError;
+use interval::{AllenRelation, Interval, Section};
+
+// how often a predicate must hold for a pair of ranges, given in both orders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Symmetry {
+    // as written, first range against the second
+    OneWay,
+    EitherWay,
+    BothWays,
+}
+
 // part one
-fn range_includes(range_a: (i32, i32), range_b: (i32, i32)) -> bool {
-    range_a.0 >= range_b.0 && range_a.1 <= range_b.1
+fn range_includes<T: Section>(range_a: Interval<T>, range_b: Interval<T>) -> bool {
+    range_a.start >= range_b.start && range_a.end <= range_b.end
 }
 
 // part two
-fn range_overlaps(range_a: (i32, i32), range_b: (i32, i32)) -> bool {
-    (range_a.0 <= range_b.1) && (range_a.1 >= range_b.0)
+fn range_overlaps<T: Section>(range_a: Interval<T>, range_b: Interval<T>) -> bool {
+    (range_a.start <= range_b.end) && (range_a.end >= range_b.start)
 }
 
-fn verify_ranges(check: fn((i32, i32), (i32, i32)) -> bool) -> usize {
-    ASSIGNMENT_PAIRS
-        .lines()
-        .filter(|line| {
-            let (elf_a, elf_b) = line
-                .split_once(",")
-                .unwrap_or_else(|| panic!("Invalid pair format"));
-
-            let a = elf_a
-                .split('-')
-                .map(|n| n.parse::<i32>().expect("oh noes"))
-                .collect::<Vec<_>>();
+// the ranges of both elves of a line
+type Pair<T> = (Interval<T>, Interval<T>);
 
-            let b = elf_b
-                .split('-')
-                .map(|n| n.parse::<i32>().expect("oh noes"))
-                .collect::<Vec<_>>();
+// the two ranges of every line, in line order; errors name the line they're on
+fn parse_pairs<T: Section>(pairs: &str) -> Result<Vec<Pair<T>>, AssignmentError> {
+    group::parse_groups(pairs)?
+        .into_iter()
+        .map(|group| match group.ranges[..] {
+            [a, b] => Ok((a, b)),
+            _ => Err(AssignmentError::NotAPair {
+                line: group.line,
+                ranges: group.ranges.len(),
+            }),
+        })
+        .collect()
+}
 
-            check((a[0], a[1]), (b[0], b[1])) || range_includes((b[0], b[1]), (a[0], a[1]))
+fn verify_ranges<T: Section>(
+    pairs: &str,
+    check: impl Fn(Interval<T>, Interval<T>) -> bool,
+    symmetry: Symmetry,
+) -> Result<usize, AssignmentError> {
+    Ok(parse_pairs(pairs)?
+        .into_iter()
+        .filter(|&(a, b)| match symmetry {
+            Symmetry::OneWay => check(a, b),
+            Symmetry::EitherWay => check(a, b) || check(b, a),
+            Symmetry::BothWays => check(a, b) && check(b, a),
         })
-        .count()
+        .count())
+}
+
+// puzzle part one: either elf's range holds the other one
+fn count_included() -> usize {
+    verify_ranges(ASSIGNMENT_PAIRS, range_includes::<i64>, Symmetry::EitherWay)
+        .expect("Invalid assignment pairs!")
+}
+
+// puzzle part two
+fn count_overlapping() -> usize {
+    verify_ranges(ASSIGNMENT_PAIRS, range_overlaps::<i64>, Symmetry::OneWay)
+        .expect("Invalid assignment pairs!")
 }
 
 fn main() {
-    println!("Included: {}", verify_ranges(range_includes));
-    println!("Overlapping: {}", verify_ranges(range_overlaps));
+    match std::env::args().nth(1).as_deref() {
+        Some("relations") => match interval::relation_counts(ASSIGNMENT_PAIRS) {
+            Ok(counts) => {
+                for (relation, count) in counts {
+                    println!("{relation:<13}  {count:>4}");
+                }
+            }
+            Err(error) => println!("{error}"),
+        },
+        Some("count") => {
+            let name = std::env::args()
+                .nth(2)
+                .unwrap_or_default()
+                .replace('-', " ");
+            let relation = AllenRelation::ALL
+                .into_iter()
+                .find(|r| r.to_string() == name)
+                .expect("Unknown relation!");
+            let symmetry = match std::env::args().nth(3).as_deref() {
+                None | Some("one-way") => Symmetry::OneWay,
+                Some("either-way") => Symmetry::EitherWay,
+                Some("both-ways") => Symmetry::BothWays,
+                Some(other) => panic!("Unknown symmetry {other}!"),
+            };
+
+            let check = |a: Interval<i64>, b| a.relation(&b) == relation;
+            match verify_ranges(ASSIGNMENT_PAIRS, check, symmetry) {
+                Ok(count) => println!("{relation} ({symmetry:?}): {count}"),
+                Err(error) => println!("{error}"),
+            }
+        }
+        Some("coverage") => match coverage::Coverage::<i64>::parse(ASSIGNMENT_PAIRS) {
+            Ok(coverage) => print!("{}", coverage.report()),
+            Err(error) => println!("{error}"),
+        },
+        Some("metrics") => match metrics::all_metrics::<i64>(ASSIGNMENT_PAIRS) {
+            Ok(metrics) if std::env::args().nth(2).as_deref() == Some("csv") => {
+                print!("{}", metrics::to_csv(&metrics))
+            }
+            Ok(metrics) => {
+                let summary = metrics::summarize(&metrics);
+                println!("Pairs: {}", summary.pairs);
+                println!("Overlapping: {}", summary.overlapping);
+                println!("Sections in both: {}", summary.intersection);
+                println!("Sections in either: {}", summary.union);
+                println!("Mean Jaccard: {:.4}", summary.mean_jaccard);
+            }
+            Err(error) => println!("{error}"),
+        },
+        Some("render") => {
+            let rendered = match std::env::args().nth(2).as_deref() {
+                Some("svg") => render::to_svg(ASSIGNMENT_PAIRS),
+                Some("plain") => render::to_ascii(ASSIGNMENT_PAIRS, false),
+                _ => render::to_ascii(ASSIGNMENT_PAIRS, true),
+            };
+            match rendered {
+                Ok(rendered) => print!("{rendered}"),
+                Err(error) => println!("{error}"),
+            }
+        }
+        Some("plan") => match index::parse_assignments::<i64>(ASSIGNMENT_PAIRS) {
+            Ok(assignments) => {
+                let plan = planner::plan_cover(&assignments);
+                let list = |elves: &[index::Elf]| {
+                    elves
+                        .iter()
+                        .map(|elf| format!("{}/{}", elf.line, elf.position))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                };
+
+                println!("Kept ({}): {}", plan.kept.len(), list(&plan.kept));
+                println!(
+                    "Released ({}): {}",
+                    plan.released.len(),
+                    list(&plan.released)
+                );
+            }
+            Err(error) => println!("{error}"),
+        },
+        Some("groups") => {
+            let input = std::env::args()
+                .nth(2)
+                .map(std::fs::read_to_string)
+                .transpose()
+                .expect("Cannot read input file!");
+
+            match group::parse_groups::<i64>(input.as_deref().unwrap_or(ASSIGNMENT_PAIRS)) {
+                Ok(groups) => {
+                    let count = |query: fn(&group::Group<i64>) -> bool| {
+                        groups.iter().filter(|g| query(g)).count()
+                    };
+                    println!("Groups: {}", groups.len());
+                    println!("Any pair overlapping: {}", count(|g| g.any_pair_overlaps()));
+                    println!(
+                        "Common section: {}",
+                        count(|g| g.common_sections().is_some())
+                    );
+                    println!(
+                        "One containing all: {}",
+                        count(|g| g.containing_all().is_some())
+                    );
+                }
+                Err(error) => println!("{error}"),
+            }
+        }
+        Some("index") => match index::SectionIndex::<i64>::parse(ASSIGNMENT_PAIRS) {
+            Ok(index) => {
+                if let Some(range) = std::env::args().nth(2) {
+                    let range = range.parse().expect("Invalid range!");
+                    for elf in index.overlapping(range) {
+                        println!("line {}, elf {}", elf.line, elf.position);
+                    }
+                } else {
+                    println!("Overlapping pairs: {}", index.overlapping_pairs().len());
+                    if let Some(busiest) = index.busiest_section() {
+                        println!(
+                            "Busiest section: {} ({} elves)",
+                            busiest.section, busiest.elves
+                        );
+                    }
+                }
+            }
+            Err(error) => println!("{error}"),
+        },
+        _ => {
+            println!("Included: {}", count_included());
+            println!("Overlapping: {}", count_overlapping());
+        }
+    }
 }
 
+// the puzzle input stays last, below the tests
+#[allow(clippy::items_after_test_module)]
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn returns_count_of_included_ranges() {
-        assert_eq!(verify_ranges(range_includes), 466);
+        assert_eq!(count_included(), 466);
     }
 
     #[test]
     fn returns_count_of_overlapping_ranges() {
-        assert_eq!(verify_ranges(range_overlaps), 865);
+        assert_eq!(count_overlapping(), 865);
+    }
+
+    #[test]
+    fn counts_asymmetric_predicates_as_asked() {
+        let starts = |a: Interval<i32>, b| a.relation(&b) == AllenRelation::Starts;
+
+        let verify = |check, symmetry| verify_ranges(ASSIGNMENT_PAIRS, check, symmetry);
+
+        assert_eq!(verify(starts, Symmetry::OneWay), Ok(90));
+        assert_eq!(verify(starts, Symmetry::EitherWay), Ok(90 + 79));
+        assert_eq!(verify(starts, Symmetry::BothWays), Ok(0));
+        assert_eq!(
+            verify_ranges(ASSIGNMENT_PAIRS, range_includes::<i32>, Symmetry::OneWay),
+            Ok(90 + 72 + 63 + 23)
+        );
+        assert_eq!(
+            verify_ranges(ASSIGNMENT_PAIRS, range_includes::<i32>, Symmetry::BothWays),
+            Ok(23)
+        );
+        assert_eq!(
+            verify_ranges(ASSIGNMENT_PAIRS, range_overlaps::<i32>, Symmetry::BothWays),
+            Ok(865)
+        );
+    }
+
+    #[test]
+    fn reports_the_line_of_bad_pairs() {
+        assert_eq!(
+            parse_pairs::<i32>("2-4,6-8\n2-3\n5-7,7-9"),
+            Err(AssignmentError::NotAPair { line: 2, ranges: 1 })
+        );
+        assert_eq!(
+            verify_ranges(
+                "2-4,6-8\n2-3,4-5\n5-7,9-7",
+                range_overlaps::<i32>,
+                Symmetry::OneWay
+            ),
+            Err(AssignmentError::Reversed {
+                line: 3,
+                range: String::from("9-7")
+            })
+        );
     }
 }
 
@@ -0,0 +1,143 @@
+use crate::group::AssignmentError;
+use crate::interval::{Interval, Section};
+use crate::parse_pairs;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PairMetrics<T> {
+    pub line: usize,
+    pub first: Interval<T>,
+    pub second: Interval<T>,
+    // sections both elves are assigned
+    pub overlap: Option<Interval<T>>,
+    pub intersection: u128,
+    // sections either elf is assigned
+    pub union: u128,
+    // intersection over union, 1 for identical ranges and 0 for disjoint ones
+    pub jaccard: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub pairs: usize,
+    pub overlapping: usize,
+    pub intersection: u128,
+    pub union: u128,
+    pub mean_jaccard: f64,
+}
+
+pub fn pair_metrics<T: Section>(
+    line: usize,
+    first: Interval<T>,
+    second: Interval<T>,
+) -> PairMetrics<T> {
+    let overlap = first.intersection(&second);
+    let intersection = overlap.map_or(0, |o| o.sections());
+    // no overflow, both counts are at most 2^64
+    let union = first.sections() + second.sections() - intersection;
+
+    PairMetrics {
+        line,
+        first,
+        second,
+        overlap,
+        intersection,
+        union,
+        jaccard: intersection as f64 / union as f64,
+    }
+}
+
+pub fn all_metrics<T: Section>(pairs: &str) -> Result<Vec<PairMetrics<T>>, AssignmentError> {
+    Ok(parse_pairs(pairs)?
+        .into_iter()
+        .enumerate()
+        .map(|(index, (first, second))| pair_metrics(index + 1, first, second))
+        .collect())
+}
+
+pub fn summarize<T>(metrics: &[PairMetrics<T>]) -> Summary {
+    Summary {
+        pairs: metrics.len(),
+        overlapping: metrics.iter().filter(|m| m.overlap.is_some()).count(),
+        intersection: metrics.iter().map(|m| m.intersection).sum(),
+        union: metrics.iter().map(|m| m.union).sum(),
+        mean_jaccard: if metrics.is_empty() {
+            0.0
+        } else {
+            metrics.iter().map(|m| m.jaccard).sum::<f64>() / metrics.len() as f64
+        },
+    }
+}
+
+// one row per pair, the overlap bounds left empty for disjoint pairs
+pub fn to_csv<T: Section>(metrics: &[PairMetrics<T>]) -> String {
+    let mut csv = String::from(
+        "line,first_start,first_end,second_start,second_end,\
+         overlap_start,overlap_end,intersection,union,jaccard\n",
+    );
+
+    for m in metrics {
+        let (overlap_start, overlap_end) = m.overlap.map_or((String::new(), String::new()), |o| {
+            (o.start.to_string(), o.end.to_string())
+        });
+        csv.push_str(&format!(
+            "{},{},{},{},{},{overlap_start},{overlap_end},{},{},{:.4}\n",
+            m.line,
+            m.first.start,
+            m.first.end,
+            m.second.start,
+            m.second.end,
+            m.intersection,
+            m.union,
+            m.jaccard
+        ));
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ASSIGNMENT_PAIRS;
+
+    #[test]
+    fn measures_a_pair() {
+        let metrics = pair_metrics(3, Interval::new(2, 6), Interval::new(4, 8));
+
+        assert_eq!(metrics.overlap, Some(Interval::new(4, 6)));
+        assert_eq!((metrics.intersection, metrics.union), (3, 7));
+        assert_eq!(metrics.jaccard, 3.0 / 7.0);
+
+        let disjoint = pair_metrics(1, Interval::new(2, 3), Interval::new(5, 5));
+        assert_eq!((disjoint.overlap, disjoint.jaccard), (None, 0.0));
+        assert_eq!(disjoint.union, 3);
+
+        let wide = pair_metrics(1, Interval::new(i64::MIN, i64::MAX), Interval::new(0, 0));
+        assert_eq!((wide.intersection, wide.union), (1, 1 << 64));
+    }
+
+    #[test]
+    fn aggregates_the_puzzle() {
+        let summary = summarize(&all_metrics::<i32>(ASSIGNMENT_PAIRS).unwrap());
+
+        assert_eq!(summary.pairs, 1000);
+        assert_eq!(summary.overlapping, 865);
+        assert!(summary.intersection <= summary.union);
+        assert!((0.0..=1.0).contains(&summary.mean_jaccard));
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = to_csv(&all_metrics::<i32>("2-6,4-8\n2-3,5-5").unwrap());
+
+        assert_eq!(
+            csv.lines().collect::<Vec<_>>(),
+            [
+                "line,first_start,first_end,second_start,second_end,\
+                 overlap_start,overlap_end,intersection,union,jaccard",
+                "1,2,6,4,8,4,6,3,7,0.4286",
+                "2,2,3,5,5,,,0,3,0.0000",
+            ]
+        );
+    }
+}
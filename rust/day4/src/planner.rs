@@ -0,0 +1,110 @@
+use crate::index::{Assignment, Elf};
+use crate::interval::Section;
+
+// the fewest elves still covering every section covered today, and the rest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverPlan {
+    pub kept: Vec<Elf>,
+    pub released: Vec<Elf>,
+}
+
+// Greedy interval cover: from the first section not covered yet, keep the elf starting
+// there or earlier whose range reaches furthest. When nobody starts there, the section
+// begins a new stretch of the camp that only needed covering if someone covered it.
+pub fn plan_cover<T: Section>(assignments: &[Assignment<T>]) -> CoverPlan {
+    let mut sorted = assignments.to_vec();
+    sorted.sort_by_key(|a| (a.range.start, a.elf));
+
+    let mut kept = Vec::new();
+    let mut next = 0;
+    // first section not covered by the kept elves, none before keeping any
+    let mut uncovered: Option<T> = None;
+
+    while next < sorted.len() {
+        if uncovered.is_some_and(|section| sorted[next].range.end < section) {
+            next += 1;
+            continue;
+        }
+        let from = uncovered.map_or(sorted[next].range.start, |section| {
+            section.max(sorted[next].range.start)
+        });
+
+        let mut furthest = next;
+        while next < sorted.len() && sorted[next].range.start <= from {
+            if sorted[next].range.end > sorted[furthest].range.end {
+                furthest = next;
+            }
+            next += 1;
+        }
+
+        kept.push(sorted[furthest].elf);
+        // nothing is left past the last section
+        match sorted[furthest].range.end.successor() {
+            Some(section) => uncovered = Some(section),
+            None => break,
+        }
+    }
+
+    kept.sort();
+    let mut released = assignments
+        .iter()
+        .map(|a| a.elf)
+        .filter(|elf| kept.binary_search(elf).is_err())
+        .collect::<Vec<_>>();
+    released.sort();
+
+    CoverPlan { kept, released }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coverage::Coverage;
+    use crate::index::parse_assignments;
+    use crate::ASSIGNMENT_PAIRS;
+
+    fn covered_by(assignments: &[Assignment<i32>], elves: &[Elf]) -> Coverage<i32> {
+        Coverage::new(
+            assignments
+                .iter()
+                .filter(|a| elves.contains(&a.elf))
+                .map(|a| a.range),
+        )
+    }
+
+    #[test]
+    fn keeps_the_fewest_elves() {
+        let assignments =
+            parse_assignments::<i32>("1-3,2-6\n4-8,5-5\n12-14,13-15\n3-7,7-8").unwrap();
+        let plan = plan_cover(&assignments);
+        let elf = |line, position| Elf { line, position };
+
+        assert_eq!(plan.kept, [elf(1, 1), elf(2, 1), elf(3, 1), elf(3, 2)]);
+        assert_eq!(plan.released, [elf(1, 2), elf(2, 2), elf(4, 1), elf(4, 2)]);
+
+        let assignments = parse_assignments::<i32>("3-4,2147483647-2147483647\n1-2147483647,5-9");
+        let plan = plan_cover(&assignments.unwrap());
+        assert_eq!(plan.kept, [elf(2, 1)]);
+        assert_eq!(plan.released, [elf(1, 1), elf(1, 2), elf(2, 2)]);
+    }
+
+    #[test]
+    fn released_elves_leave_no_section_uncovered() {
+        let assignments = parse_assignments(ASSIGNMENT_PAIRS).unwrap();
+        let plan = plan_cover(&assignments);
+
+        assert_eq!(plan.kept.len() + plan.released.len(), 2000);
+        assert_eq!(
+            covered_by(&assignments, &plan.kept).union(),
+            Coverage::new(assignments.iter().map(|a| a.range)).union()
+        );
+        // line 548 holds a 1-99 assignment, including every other one
+        assert_eq!(
+            plan.kept,
+            [Elf {
+                line: 548,
+                position: 2
+            }]
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use crate::group::AssignmentError;
+use crate::interval::{Interval, Section};
+use crate::{parse_pairs, range_includes, range_overlaps, Pair};
+
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const RESET: &str = "\x1b[0m";
+
+// svg layout, in pixels
+const SECTION_WIDTH: u128 = 6;
+const BAR_HEIGHT: u128 = 4;
+const PAIR_GAP: u128 = 3;
+const MARGIN: u128 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PairKind {
+    // one range includes the other, part one
+    Contained,
+    // only part two
+    Overlapping,
+    Disjoint,
+}
+
+impl PairKind {
+    pub fn of<T: Section>(a: Interval<T>, b: Interval<T>) -> Self {
+        if range_includes(a, b) || range_includes(b, a) {
+            PairKind::Contained
+        } else if range_overlaps(a, b) {
+            PairKind::Overlapping
+        } else {
+            PairKind::Disjoint
+        }
+    }
+
+    fn ansi(self) -> Option<&'static str> {
+        match self {
+            PairKind::Contained => Some(GREEN),
+            PairKind::Overlapping => Some(YELLOW),
+            PairKind::Disjoint => None,
+        }
+    }
+
+    fn fill(self) -> &'static str {
+        match self {
+            PairKind::Contained => "#2e7d32",
+            PairKind::Overlapping => "#f9a825",
+            PairKind::Disjoint => "#90a4ae",
+        }
+    }
+}
+
+// the sections spanned by every pair, so all bars line up; like the puzzle's diagrams,
+// they start at section 1 unless some range starts before
+fn camp<T: Section>(pairs: &[Pair<T>]) -> Option<Interval<T>> {
+    let start = pairs
+        .iter()
+        .map(|(a, b)| a.start.min(b.start))
+        .min()?
+        .min(T::ONE);
+    let end = pairs.iter().map(|(a, b)| a.end.max(b.end)).max()?;
+    Some(Interval::new(start, end))
+}
+
+// like the puzzle's diagrams: the last digit of every assigned section, dots elsewhere
+fn bar<T: Section>(camp: Interval<T>, range: Interval<T>) -> String {
+    camp.iter()
+        .map(|section| {
+            if range.start <= section && section <= range.end {
+                section.to_string().pop().unwrap()
+            } else {
+                '.'
+            }
+        })
+        .collect()
+}
+
+// two bars per pair and a blank line in between, colored by `PairKind` unless `color` is off
+pub fn to_ascii(pairs: &str, color: bool) -> Result<String, AssignmentError> {
+    let pairs = parse_pairs::<i64>(pairs)?;
+    let Some(camp) = camp(&pairs) else {
+        return Ok(String::new());
+    };
+
+    let mut ascii = String::new();
+    for (a, b) in &pairs {
+        let (on, off) = match PairKind::of(*a, *b).ansi() {
+            Some(code) if color => (code, RESET),
+            _ => ("", ""),
+        };
+        for range in [a, b] {
+            ascii.push_str(&format!("{on}{}{off}  {range}\n", bar(camp, *range)));
+        }
+        ascii.push('\n');
+    }
+
+    Ok(ascii)
+}
+
+pub fn to_svg(pairs: &str) -> Result<String, AssignmentError> {
+    let pairs = parse_pairs::<i64>(pairs)?;
+    let camp = camp(&pairs).unwrap_or(Interval::new(0, 0));
+    let pair_height = 2 * BAR_HEIGHT + PAIR_GAP;
+    let width = camp.sections() * SECTION_WIDTH + 2 * MARGIN;
+    let height = pairs.len() as u128 * pair_height + 2 * MARGIN;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\">\n"
+    );
+
+    for (index, (a, b)) in pairs.iter().enumerate() {
+        let fill = PairKind::of(*a, *b).fill();
+        for (row, range) in [a, b].into_iter().enumerate() {
+            let x = MARGIN + (i64::count(camp.start, range.start) - 1) * SECTION_WIDTH;
+            let y = MARGIN + index as u128 * pair_height + row as u128 * BAR_HEIGHT;
+            let bar_width = range.sections() * SECTION_WIDTH;
+            svg.push_str(&format!(
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{bar_width}\" height=\"{BAR_HEIGHT}\" \
+                 fill=\"{fill}\"><title>line {}: {range}</title></rect>\n",
+                index + 1
+            ));
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ASSIGNMENT_PAIRS;
+
+    // the puzzle's example
+    const EXAMPLE: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
+
+    #[test]
+    fn draws_the_puzzle_diagrams() {
+        let ascii = to_ascii(EXAMPLE, false).unwrap();
+
+        assert!(ascii.starts_with(".234.....  2-4\n.....678.  6-8\n\n.23......  2-3\n"));
+        assert!(ascii.ends_with(".23456...  2-6\n...45678.  4-8\n\n"));
+    }
+
+    #[test]
+    fn colors_contained_and_overlapping_pairs() {
+        let ascii = to_ascii(EXAMPLE, true).unwrap();
+        let lines = ascii.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines[0], ".234.....  2-4");
+        assert_eq!(lines[6], format!("{YELLOW}....567..{RESET}  5-7"));
+        assert_eq!(lines[9], format!("{GREEN}.2345678.{RESET}  2-8"));
+        assert_eq!(
+            ascii.matches(GREEN).count(),
+            2 * 2,
+            "2-8,3-7 and 6-6,4-6 are contained"
+        );
+    }
+
+    #[test]
+    fn draws_the_whole_camp_as_svg() {
+        let svg = to_svg(ASSIGNMENT_PAIRS).unwrap();
+
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="614""#));
+        assert_eq!(svg.matches("<rect ").count(), 2000);
+        assert_eq!(svg.matches(PairKind::Contained.fill()).count(), 2 * 466);
+        assert_eq!(
+            svg.matches(PairKind::Disjoint.fill()).count(),
+            2 * (1000 - 865)
+        );
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
@@ -1,29 +1,34 @@
-use std::{io, ops::IndexMut};
+use std::{error::Error, fmt, io, ops::IndexMut};
 
 use lazy_static::lazy_static;
 use regex::Regex;
 
 #[derive(Debug)]
 pub struct Move {
+    // 1-based line of the move in the whole input
+    line: usize,
     quantity: usize,
     from_stack: usize,
     to_stack: usize,
 }
 
-impl TryFrom<&str> for Move {
+impl TryFrom<(usize, &str)> for Move {
     type Error = MoveCratesError;
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
+    fn try_from((line, value): (usize, &str)) -> Result<Self, Self::Error> {
         lazy_static! {
             static ref MOVES: Regex =
-                Regex::new(r"move (?P<qty>\d+) from (?P<from_stack>\d) to (?P<to_stack>\d)")
+                Regex::new(r"^move (?P<qty>\d+) from (?P<from_stack>\d+) to (?P<to_stack>\d+)$")
                     .unwrap();
         }
+        let malformed = || MoveCratesError::MalformedMove {
+            line,
+            content: value.to_owned(),
+        };
+
         MOVES
             .captures(value)
-            .map_or(Err(MoveCratesError::BadInput), |moves_captures| {
-                Ok(moves_captures)
-            })
+            .ok_or_else(malformed)
             .and_then(|capture| {
                 if let (Some(quantity), Some(from_stack), Some(to_stack)) = (
                     capture
@@ -37,19 +42,20 @@ impl TryFrom<&str> for Move {
                         .and_then(|c| c.as_str().parse::<usize>().ok()),
                 ) {
                     Ok(Self {
+                        line,
                         quantity,
                         from_stack,
                         to_stack,
                     })
                 } else {
-                    Err(Self::Error::BadInput)
+                    Err(malformed())
                 }
             })
     }
 }
 
 pub trait CrateMover {
-    // TODO: this should return a Result<(), BadMoveError>
+    // leaves the stacks untouched when the move fails
     fn interpret_move(
         &self,
         a_move: &Move,
@@ -57,9 +63,110 @@ pub trait CrateMover {
     ) -> Result<(), MoveCratesError>;
 }
 
-#[derive(Debug)]
+// stacks are numbered from 1, line numbers are 1-based, and every stack is written bottom
+// to top, as it stood when things went wrong
+#[derive(Debug, PartialEq, Eq)]
 pub enum MoveCratesError {
-    BadInput,
+    MalformedMove {
+        line: usize,
+        content: String,
+    },
+    UnknownStack {
+        line: usize,
+        stack: usize,
+        stacks: Vec<String>,
+    },
+    InsufficientCrates {
+        line: usize,
+        stack: usize,
+        requested: usize,
+        available: usize,
+        stacks: Vec<String>,
+    },
+    // nothing left on top of this stack once every move is done
+    EmptyStack {
+        stack: usize,
+        stacks: Vec<String>,
+    },
+    MalformedDrawing {
+        line: usize,
+        content: String,
+    },
+}
+
+impl fmt::Display for MoveCratesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveCratesError::MalformedMove { line, content } => write!(
+                f,
+                "line {line}: expected \"move <n> from <stack> to <stack>\", found \"{content}\""
+            ),
+            MoveCratesError::UnknownStack {
+                line,
+                stack,
+                stacks,
+            } => write!(
+                f,
+                "line {line}: there is no stack {stack}, only {} in {stacks:?}",
+                stacks.len()
+            ),
+            MoveCratesError::InsufficientCrates {
+                line,
+                stack,
+                requested,
+                available,
+                stacks,
+            } => write!(
+                f,
+                "line {line}: cannot move {requested} crates from stack {stack} holding \
+                 {available} in {stacks:?}"
+            ),
+            MoveCratesError::EmptyStack { stack, stacks } => {
+                write!(f, "stack {stack} ends up empty in {stacks:?}")
+            }
+            MoveCratesError::MalformedDrawing { line, content } => {
+                write!(f, "line {line}: unexpected drawing line \"{content}\"")
+            }
+        }
+    }
+}
+
+impl Error for MoveCratesError {}
+
+fn snapshot(stacks: &[Vec<&str>]) -> Vec<String> {
+    stacks.iter().map(|s| s.concat()).collect()
+}
+
+// checks both stacks before touching either, then takes the crates off the top in the
+// order they were stacked
+fn take_crates<'a>(
+    a_move: &Move,
+    stacks: &mut [Vec<&'a str>],
+) -> Result<Vec<&'a str>, MoveCratesError> {
+    for stack in [a_move.from_stack, a_move.to_stack] {
+        if stack == 0 || stack > stacks.len() {
+            return Err(MoveCratesError::UnknownStack {
+                line: a_move.line,
+                stack,
+                stacks: snapshot(stacks),
+            });
+        }
+    }
+
+    let available = stacks[a_move.from_stack - 1].len();
+    if a_move.quantity > available {
+        return Err(MoveCratesError::InsufficientCrates {
+            line: a_move.line,
+            stack: a_move.from_stack,
+            requested: a_move.quantity,
+            available,
+            stacks: snapshot(stacks),
+        });
+    }
+
+    Ok(stacks[a_move.from_stack - 1]
+        .drain(available - a_move.quantity..)
+        .collect())
 }
 
 struct CrateMover9000;
@@ -71,21 +178,8 @@ impl CrateMover for CrateMover9000 {
         a_move: &Move,
         stacks: &mut Vec<Vec<&str>>,
     ) -> Result<(), MoveCratesError> {
-        let remove_from = stacks
-            .get_mut(a_move.from_stack - 1)
-            .ok_or(MoveCratesError::BadInput)?;
-
-        let to_add = if a_move.quantity <= remove_from.len() {
-            let slice_start_index = remove_from.len() - a_move.quantity;
-            let to_add = remove_from.drain(slice_start_index..).collect::<Vec<_>>();
-            Ok(to_add)
-        } else {
-            Err(MoveCratesError::BadInput)
-        }?;
-
-        let add_to = stacks
-            .get_mut(a_move.to_stack - 1)
-            .ok_or(MoveCratesError::BadInput)?;
+        let to_add = take_crates(a_move, stacks)?;
+        let add_to = stacks.index_mut(a_move.to_stack - 1);
 
         for item in to_add.iter().rev() {
             add_to.push(item);
@@ -101,21 +195,8 @@ impl CrateMover for CrateMover9001 {
         a_move: &Move,
         stacks: &mut Vec<Vec<&str>>,
     ) -> Result<(), MoveCratesError> {
-        let remove_from = stacks
-            .get_mut(a_move.from_stack - 1)
-            .ok_or(MoveCratesError::BadInput)?;
-
-        let to_add = if a_move.quantity <= remove_from.len() {
-            let slice_start_index = remove_from.len() - a_move.quantity;
-            let to_add = remove_from.drain(slice_start_index..).collect::<Vec<_>>();
-            Ok(to_add)
-        } else {
-            Err(MoveCratesError::BadInput)
-        }?;
-
-        let add_to = stacks
-            .get_mut(a_move.to_stack - 1)
-            .ok_or(MoveCratesError::BadInput)?;
+        let to_add = take_crates(a_move, stacks)?;
+        let add_to = stacks.index_mut(a_move.to_stack - 1);
 
         for item in to_add.iter() {
             add_to.push(item);
@@ -126,58 +207,94 @@ impl CrateMover for CrateMover9001 {
 }
 
 fn main() -> io::Result<()> {
-    let result_9000 = move_crane(&CrateMover9000 {});
-    let result_9001 = move_crane(&CrateMover9001 {});
-    println!("{}", result_9000.unwrap());
-    println!("{}", result_9001.unwrap());
+    for result in [
+        move_crane(&CrateMover9000 {}),
+        move_crane(&CrateMover9001 {}),
+    ] {
+        match result {
+            Ok(top_crates) => println!("{top_crates}"),
+            Err(error) => println!("{error}"),
+        }
+    }
     Ok(())
 }
 
 // see https://oswalt.dev/2021/06/polymorphism-in-rust/ for signature explanation
 fn move_crane(crate_mover: &dyn CrateMover) -> Result<String, MoveCratesError> {
-    if let Some((crates, moves)) = CRATES.split_once("\n\n") {
+    move_crates(crate_mover, CRATES)
+}
+
+fn move_crates(crate_mover: &dyn CrateMover, input: &str) -> Result<String, MoveCratesError> {
+    if let Some((crates, moves)) = input.split_once("\n\n") {
         let mut stacks = parse_stacks(crates)?;
-        let moves = parse_moves(moves).unwrap();
+        // the drawing, then the blank line
+        let moves = parse_moves(moves, crates.lines().count() + 2)?;
 
         for a_move in moves {
             crate_mover.interpret_move(&a_move, &mut stacks)?;
         }
 
-        Ok(stacks
+        stacks
             .iter()
-            .map(|s| *s.last().unwrap())
-            .collect::<Vec<_>>()
-            .join(""))
+            .enumerate()
+            .map(|(index, s)| {
+                s.last()
+                    .copied()
+                    .ok_or_else(|| MoveCratesError::EmptyStack {
+                        stack: index + 1,
+                        stacks: snapshot(&stacks),
+                    })
+            })
+            .collect()
     } else {
-        Err(MoveCratesError::BadInput)
+        // the drawing never ends
+        let lines = input.lines().collect::<Vec<_>>();
+        Err(MoveCratesError::MalformedDrawing {
+            line: lines.len(),
+            content: lines.last().unwrap_or(&"").to_string(),
+        })
     }
 }
 
-fn parse_moves(moves: &str) -> Result<Vec<Move>, MoveCratesError> {
+fn parse_moves(moves: &str, first_line: usize) -> Result<Vec<Move>, MoveCratesError> {
     moves
         .lines()
-        .map(Move::try_from)
+        .enumerate()
+        .map(|(index, line)| Move::try_from((first_line + index, line)))
         // transforms Vec<Result<..., ...>> into Result<Vec<...>, ...>
-        .into_iter()
         .collect::<Result<Vec<Move>, MoveCratesError>>()
 }
 
 fn parse_stacks(crates: &str) -> Result<Vec<Vec<&str>>, MoveCratesError> {
     lazy_static! {
-        static ref INDEXES: Regex = Regex::new(r"\s+(\d)").unwrap();
+        static ref INDEXES_LINE: Regex = Regex::new(r"^(\s+\d+)+\s*$").unwrap();
+        static ref INDEXES: Regex = Regex::new(r"\s+(\d+)").unwrap();
         static ref CRATES_LINE: Regex = Regex::new(r"(\s{3}|\[([A-Z])\])\s?").unwrap();
     }
 
-    let num_of_stacks = crates
-        .lines()
+    let lines = crates.lines().collect::<Vec<_>>();
+    let malformed = |index: usize| MoveCratesError::MalformedDrawing {
+        line: index + 1,
+        content: lines.get(index).unwrap_or(&"").to_string(),
+    };
+
+    let num_of_stacks = lines
         .last()
+        .filter(|l| INDEXES_LINE.is_match(l))
         .map(|l| INDEXES.captures_iter(l).collect::<Vec<_>>().len())
-        .ok_or(MoveCratesError::BadInput)?;
+        .ok_or_else(|| malformed(lines.len().saturating_sub(1)))?;
 
     let mut stacks: Vec<Vec<&str>> = vec![Vec::<&str>::new(); num_of_stacks];
 
-    for line in crates.lines().rev().skip(1) {
-        for (index, capture) in CRATES_LINE.captures_iter(line).enumerate() {
+    for (line_index, line) in lines.iter().enumerate().rev().skip(1) {
+        let captures = CRATES_LINE.captures_iter(line).collect::<Vec<_>>();
+        // every character must belong to a crate or a gap, and no crate past the last stack
+        let covered = captures.iter().map(|c| c[0].len()).sum::<usize>();
+        if covered != line.len() || captures.len() > num_of_stacks {
+            return Err(malformed(line_index));
+        }
+
+        for (index, capture) in captures.iter().enumerate() {
             if let Some(item) = capture.get(2).map(|c| c.as_str()) {
                 stacks.index_mut(index).push(item);
             }
@@ -187,10 +304,14 @@ fn parse_stacks(crates: &str) -> Result<Vec<Vec<&str>>, MoveCratesError> {
     Ok(stacks)
 }
 
+// the puzzle input stays last, below the tests
+#[allow(clippy::items_after_test_module)]
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE_DRAWING: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ";
+
     #[test]
     fn part_1() {
         assert_eq!(move_crane(&CrateMover9000 {}).unwrap(), "VQZNJMWTR");
@@ -200,6 +321,79 @@ mod tests {
     fn part_2() {
         assert_eq!(move_crane(&CrateMover9001 {}).unwrap(), "NLCDCLVMQ");
     }
+
+    #[test]
+    fn reports_bad_moves_with_their_line() {
+        let input = format!(
+            "{EXAMPLE_DRAWING}\n\nmove 1 from 2 to 1\nmove 1 from 2 to 1\nmove 3 fro 1 to 3"
+        );
+        assert_eq!(
+            move_crates(&CrateMover9000 {}, &input),
+            Err(MoveCratesError::MalformedMove {
+                line: 8,
+                content: String::from("move 3 fro 1 to 3")
+            })
+        );
+
+        let input = format!("{EXAMPLE_DRAWING}\n\nmove 1 from 2 to 1\nmove 1 from 4 to 1");
+        assert_eq!(
+            move_crates(&CrateMover9000 {}, &input),
+            Err(MoveCratesError::UnknownStack {
+                line: 7,
+                stack: 4,
+                stacks: vec![String::from("ZND"), String::from("MC"), String::from("P")]
+            })
+        );
+    }
+
+    #[test]
+    fn reports_missing_crates_and_empty_stacks() {
+        let input = format!("{EXAMPLE_DRAWING}\n\nmove 4 from 2 to 1");
+        assert_eq!(
+            move_crates(&CrateMover9001 {}, &input),
+            Err(MoveCratesError::InsufficientCrates {
+                line: 6,
+                stack: 2,
+                requested: 4,
+                available: 3,
+                stacks: vec![String::from("ZN"), String::from("MCD"), String::from("P")]
+            })
+        );
+
+        let input = format!("{EXAMPLE_DRAWING}\n\nmove 1 from 3 to 1");
+        let error = move_crates(&CrateMover9000 {}, &input).unwrap_err();
+        assert_eq!(
+            error,
+            MoveCratesError::EmptyStack {
+                stack: 3,
+                stacks: vec![String::from("ZNP"), String::from("MCD"), String::new()]
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            r#"stack 3 ends up empty in ["ZNP", "MCD", ""]"#
+        );
+    }
+
+    #[test]
+    fn reports_malformed_drawings() {
+        let input = "    [D]    \n[N] (C)    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1";
+        assert_eq!(
+            move_crates(&CrateMover9000 {}, input),
+            Err(MoveCratesError::MalformedDrawing {
+                line: 2,
+                content: String::from("[N] (C)    ")
+            })
+        );
+        assert!(matches!(
+            move_crates(&CrateMover9000 {}, "[A]\n 1 \nmove 1 from 1 to 1"),
+            Err(MoveCratesError::MalformedDrawing { line: 3, .. })
+        ));
+        assert!(matches!(
+            move_crates(&CrateMover9000 {}, "[A]\n\nmove 1 from 1 to 1"),
+            Err(MoveCratesError::MalformedDrawing { line: 1, .. })
+        ));
+    }
 }
 
 const CRATES: &str = "            [L] [M]         [M]